@@ -0,0 +1,62 @@
+use room_visual_ext::{Rasterizer, RecordingBackend, RoomVisualExt, SvgRenderer};
+use screeps::StructureType;
+
+/// Draws a small base plan outside of the game and saves it as an SVG or a PNG image, depending on
+/// the file's extension.
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "room_visual_ext_example.svg".to_string());
+
+    let mut room_visual_ext = RoomVisualExt::with_backend(RecordingBackend::new());
+
+    for x in 21..30 {
+        room_visual_ext.structure(x as f32, 25f32, StructureType::Road, 1.0);
+    }
+    for y in 21..30 {
+        if y != 25 {
+            room_visual_ext.structure(25f32, y as f32, StructureType::Road, 1.0);
+        }
+    }
+
+    room_visual_ext.structure(24f32, 24f32, StructureType::Spawn, 1.0);
+    room_visual_ext.structure(26f32, 24f32, StructureType::Storage, 1.0);
+    room_visual_ext.structure(24f32, 26f32, StructureType::Terminal, 1.0);
+    room_visual_ext.structure(26f32, 26f32, StructureType::Factory, 1.0);
+    room_visual_ext.structure(23f32, 24f32, StructureType::Tower, 1.0);
+    room_visual_ext.structure(27f32, 24f32, StructureType::Link, 1.0);
+    room_visual_ext.structure(23f32, 26f32, StructureType::PowerSpawn, 1.0);
+    room_visual_ext.structure(27f32, 26f32, StructureType::Nuker, 1.0);
+    room_visual_ext.structure(22f32, 23f32, StructureType::Lab, 1.0);
+    room_visual_ext.structure(28f32, 23f32, StructureType::Observer, 1.0);
+    room_visual_ext.structure(29f32, 25f32, StructureType::Container, 1.0);
+    for (x, y) in [
+        (23, 23),
+        (24, 23),
+        (26, 23),
+        (27, 23),
+        (23, 27),
+        (24, 27),
+        (26, 27),
+        (27, 27),
+    ] {
+        room_visual_ext.structure(x as f32, y as f32, StructureType::Extension, 1.0);
+    }
+    for x in 21..30 {
+        room_visual_ext.structure(x as f32, 21f32, StructureType::Wall, 1.0);
+        room_visual_ext.structure(x as f32, 29f32, StructureType::Rampart, 1.0);
+    }
+
+    let visuals = &room_visual_ext.room_visual.visuals;
+    if path.ends_with(".png") {
+        Rasterizer::new()
+            .pixels_per_tile(32)
+            .write_png(visuals, &path)?;
+    } else {
+        SvgRenderer::new()
+            .pixels_per_tile(32.0)
+            .write(visuals, &path)?;
+    }
+    println!("Saved the plan to {}.", path);
+    Ok(())
+}
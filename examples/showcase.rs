@@ -36,7 +36,7 @@ pub fn game_loop() {
 
         // RoomXY is also supported.
         room_visual_ext.structure_roomxy(
-            unsafe { RoomXY::unchecked_new(13 + x_offset as u8, 14) },
+            RoomXY::try_from((13 + x_offset as u8, 14)).unwrap(),
             StructureType::Spawn,
             opacity,
         );
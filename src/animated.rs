@@ -0,0 +1,109 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::PolyStyle;
+use std::f32::consts::FRAC_PI_2;
+use std::rc::Rc;
+
+/// Style of an animated position marker, a builder like the `screeps` styles.
+#[derive(Clone, Debug)]
+pub struct AnimatedPositionStyle {
+    color: Option<String>,
+    radius: f32,
+    frames: u32,
+    opacity: f32,
+}
+
+impl Default for AnimatedPositionStyle {
+    fn default() -> Self {
+        AnimatedPositionStyle {
+            color: None,
+            radius: 0.75,
+            frames: 6,
+            opacity: 0.5,
+        }
+    }
+}
+
+impl AnimatedPositionStyle {
+    /// Color of the marker. Defaults to `Theme::position_marker`.
+    pub fn color(mut self, val: &str) -> AnimatedPositionStyle {
+        self.color = Some(val.to_string());
+        self
+    }
+
+    /// Base radius of the marker in tiles. Defaults to 0.75.
+    pub fn radius(mut self, val: f32) -> AnimatedPositionStyle {
+        self.radius = val;
+        self
+    }
+
+    /// Number of ticks of a single cycle of the animation. Defaults to 6.
+    pub fn frames(mut self, val: u32) -> AnimatedPositionStyle {
+        self.frames = val.max(1);
+        self
+    }
+
+    pub fn opacity(mut self, val: f32) -> AnimatedPositionStyle {
+        self.opacity = val;
+        self
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a diamond marker around the (x, y) tile that rotates and pulses with the game tick,
+    /// like the `animatedPosition` of screepers' RoomVisual. The shape depends only on the tick, so
+    /// it animates over consecutive ticks without storing any state. Pass `game::time()` as the
+    /// tick.
+    pub fn animated_position(
+        &mut self,
+        x: f32,
+        y: f32,
+        tick: u32,
+        style: Option<AnimatedPositionStyle>,
+    ) {
+        let theme = Rc::clone(&self.theme);
+        let style = style.unwrap_or_default();
+        let frame = tick % style.frames;
+        let angle = frame as f32 * FRAC_PI_2 / style.frames as f32;
+        let (sin, cos) = angle.sin_cos();
+        let size_mod = (frame as f32 - style.frames as f32 / 2.0).abs() / 10.0;
+        let radius = style.radius * (1.0 + size_mod);
+
+        let marker_points = [
+            (0.0, -radius),
+            (radius, 0.0),
+            (0.0, radius),
+            (-radius, 0.0),
+            (0.0, -radius),
+        ]
+        .into_iter()
+        .map(|(point_x, point_y)| {
+            (
+                x + point_x * cos - point_y * sin,
+                y + point_x * sin + point_y * cos,
+            )
+        })
+        .collect();
+        let marker_poly_style = PolyStyle::default()
+            .fill("transparent")
+            .stroke(style.color.as_deref().unwrap_or(&theme.position_marker))
+            .opacity(style.opacity);
+        self.poly(marker_points, Some(marker_poly_style));
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use screeps::{CircleStyle, LineStyle, PolyStyle, RectStyle, RoomVisual, TextStyle};
+
+/// Target of the primitives that `RoomVisualExt` draws.
+///
+/// The methods mirror the drawing methods of `RoomVisual`, which is the default implementation.
+/// Other implementations may record, export or render the primitives outside of the game.
+pub trait RoomVisualBackend {
+    fn circle(&mut self, x: f32, y: f32, style: Option<CircleStyle>);
+
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), style: Option<LineStyle>);
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, style: Option<RectStyle>);
+
+    fn poly(&mut self, points: Vec<(f32, f32)>, style: Option<PolyStyle>);
+
+    fn text(&mut self, x: f32, y: f32, text: String, style: Option<TextStyle>);
+}
+
+impl RoomVisualBackend for RoomVisual {
+    fn circle(&mut self, x: f32, y: f32, style: Option<CircleStyle>) {
+        RoomVisual::circle(self, x, y, style);
+    }
+
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), style: Option<LineStyle>) {
+        RoomVisual::line(self, from, to, style);
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, style: Option<RectStyle>) {
+        RoomVisual::rect(self, x, y, width, height, style);
+    }
+
+    fn poly(&mut self, points: Vec<(f32, f32)>, style: Option<PolyStyle>) {
+        RoomVisual::poly(self, points, style);
+    }
+
+    fn text(&mut self, x: f32, y: f32, text: String, style: Option<TextStyle>) {
+        RoomVisual::text(self, x, y, text, style);
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::json::to_json_value;
+use screeps::Visual;
+use std::collections::HashMap;
+
+/// Limit of serialized visual data per room imposed by the game, 500 kB.
+pub const ROOM_VISUAL_SIZE_LIMIT: usize = 500 * 1024;
+
+/// What happens with the draws once the soft cap of a `SizeBudget` is reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverCapPolicy {
+    /// Primitives that would exceed the cap are not drawn.
+    Reject,
+    /// Structures are drawn at `DetailLevel::Minimal`. Primitives that would exceed the game's
+    /// limit are not drawn.
+    Downgrade,
+}
+
+/// Running estimate of the serialized visual data emitted to a room.
+///
+/// The primitives are attributed to the current category, so that it is possible to tell which
+/// part of the code uses the most data or had its draws rejected.
+#[derive(Clone, Debug)]
+pub struct SizeBudget {
+    cap: Option<usize>,
+    policy: OverCapPolicy,
+    used: usize,
+    rejected: usize,
+    category: String,
+    used_by_category: HashMap<String, usize>,
+    rejected_by_category: HashMap<String, usize>,
+}
+
+impl Default for SizeBudget {
+    fn default() -> Self {
+        SizeBudget {
+            cap: None,
+            policy: OverCapPolicy::Reject,
+            used: 0,
+            rejected: 0,
+            category: String::new(),
+            used_by_category: HashMap::new(),
+            rejected_by_category: HashMap::new(),
+        }
+    }
+}
+
+impl SizeBudget {
+    /// Estimated size in bytes of all drawn primitives.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Soft cap on the size, if set.
+    pub fn cap(&self) -> Option<usize> {
+        self.cap
+    }
+
+    pub fn policy(&self) -> OverCapPolicy {
+        self.policy
+    }
+
+    /// Bytes left until the soft cap or, if it is not set, until the game's limit.
+    pub fn remaining(&self) -> usize {
+        self.cap
+            .unwrap_or(ROOM_VISUAL_SIZE_LIMIT)
+            .min(ROOM_VISUAL_SIZE_LIMIT)
+            .saturating_sub(self.used)
+    }
+
+    /// Fraction of the soft cap or, if it is not set, of the game's limit that is used.
+    pub fn used_fraction(&self) -> f32 {
+        let limit = self
+            .cap
+            .unwrap_or(ROOM_VISUAL_SIZE_LIMIT)
+            .min(ROOM_VISUAL_SIZE_LIMIT);
+        if limit == 0 {
+            1.0
+        } else {
+            self.used as f32 / limit as f32
+        }
+    }
+
+    /// Whether the soft cap is set and reached.
+    pub fn is_over_cap(&self) -> bool {
+        self.cap.is_some_and(|cap| self.used >= cap)
+    }
+
+    /// Number of primitives that were not drawn due to the cap or the game's limit.
+    pub fn rejected(&self) -> usize {
+        self.rejected
+    }
+
+    /// Category to which subsequent primitives are attributed. Empty by default.
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+
+    /// Estimated size in bytes of primitives drawn in each category.
+    pub fn used_by_category(&self) -> &HashMap<String, usize> {
+        &self.used_by_category
+    }
+
+    /// Number of primitives rejected in each category.
+    pub fn rejected_by_category(&self) -> &HashMap<String, usize> {
+        &self.rejected_by_category
+    }
+
+    pub(crate) fn set_cap(&mut self, cap: Option<usize>, policy: OverCapPolicy) {
+        self.cap = cap;
+        self.policy = policy;
+    }
+
+    pub(crate) fn set_category(&mut self, category: &str) {
+        self.category = category.to_string();
+    }
+
+    /// Accounts the primitive if it fits and returns whether it should be drawn.
+    pub(crate) fn spend(&mut self, visual: &Visual) -> bool {
+        let size = estimated_size(visual);
+        let limit = match (self.cap, self.policy) {
+            (Some(cap), OverCapPolicy::Reject) => cap.min(ROOM_VISUAL_SIZE_LIMIT),
+            _ => ROOM_VISUAL_SIZE_LIMIT,
+        };
+        if self.used + size > limit {
+            self.rejected += 1;
+            *self
+                .rejected_by_category
+                .entry(self.category.clone())
+                .or_default() += 1;
+            false
+        } else {
+            self.used += size;
+            *self
+                .used_by_category
+                .entry(self.category.clone())
+                .or_default() += size;
+            true
+        }
+    }
+}
+
+/// Estimated size in bytes of the primitive serialized by the game.
+///
+/// The game stores each primitive as a line of JSON created by `JSON.stringify`.
+pub fn estimated_size(visual: &Visual) -> usize {
+    let value = to_json_value(visual).expect("expect serialize visual");
+    value.js_len() + 1
+}
@@ -0,0 +1,163 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::controller::arc_points;
+use crate::deferred::structure_layer;
+use crate::{DetailLevel, RecordedVisual, RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, LineDrawStyle, LineStyle, PolyStyle, RectStyle, StructureType};
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::rc::Rc;
+
+/// Width of the outlines of the structures of construction sites.
+const OUTLINE_WIDTH: f32 = 0.05;
+/// Number of segments of the outlines of circles of the structures of construction sites.
+const OUTLINE_SEGMENTS: usize = 12;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a construction site of given structure: the construction site ring, filled clockwise
+    /// with `progress / progress_total` like in the game, and the structure's glyph on top of it,
+    /// with each of its parts replaced by a dashed outline in `Theme::construction_site`.
+    ///
+    /// Unlike with `structure`, road construction sites are not connected to roads.
+    pub fn construction_site(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        progress: u32,
+        progress_total: u32,
+        opacity: f32,
+    ) {
+        self.in_layer(structure_layer(structure_type), |ext| {
+            ext.draw_construction_site(x, y, structure_type, progress, progress_total, opacity)
+        });
+    }
+
+    fn draw_construction_site(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        progress: u32,
+        progress_total: u32,
+        opacity: f32,
+    ) {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.outline_color();
+        let ring_circle_style = CircleStyle::default()
+            .radius(0.3)
+            .fill(&theme.dark)
+            .stroke(&theme.light)
+            .stroke_width(0.08)
+            .opacity(opacity);
+        self.circle(x, y, Some(ring_circle_style));
+
+        // The outline goes on top of the ring, so that the ring does not hide small structures.
+        let previous_site_outline = self.site_outline.replace(theme.construction_site.clone());
+        self.draw_structure(x, y, structure_type, opacity);
+        self.site_outline = previous_site_outline;
+
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            return;
+        }
+
+        let progress = if progress_total == 0 {
+            0.0
+        } else {
+            (progress as f32 / progress_total as f32).min(1.0)
+        };
+        if progress > 0.0 {
+            let end_angle = -FRAC_PI_2 + progress * 2.0 * PI;
+            let arc_segments = (progress * 16.0).ceil() as usize;
+            let progress_poly_style = PolyStyle::default()
+                .fill("transparent")
+                .stroke(&outline)
+                .stroke_width(0.1)
+                .opacity(opacity);
+            self.poly(
+                arc_points(x, y, 0.3, -FRAC_PI_2, end_angle, arc_segments + 1),
+                Some(progress_poly_style),
+            );
+        }
+
+        let center_circle_style = CircleStyle::default()
+            .radius(0.1)
+            .fill(&theme.light)
+            .opacity(opacity);
+        self.circle(x, y, Some(center_circle_style));
+    }
+
+    /// Draws the dashed outline of a primitive of the structure of a construction site instead of
+    /// the primitive. Texts are not drawn.
+    pub(crate) fn draw_site_outline(&mut self, visual: RecordedVisual) {
+        let color = match self.site_outline.take() {
+            Some(color) => color,
+            None => return,
+        };
+        let opacity = visual.opacity();
+        let radius = visual.radius();
+        match visual {
+            RecordedVisual::Circle { x, y, .. } => {
+                // Circles cannot be dashed, so the outline is a polygon.
+                let outline_poly_style = PolyStyle::default()
+                    .fill("transparent")
+                    .stroke(&color)
+                    .stroke_width(OUTLINE_WIDTH)
+                    .line_style(LineDrawStyle::Dashed)
+                    .opacity(opacity);
+                self.poly(
+                    arc_points(x, y, radius, 0.0, 2.0 * PI, OUTLINE_SEGMENTS + 1),
+                    Some(outline_poly_style),
+                );
+            }
+            RecordedVisual::Line { from, to, .. } => {
+                let outline_line_style = LineStyle::default()
+                    .color(&color)
+                    .width(OUTLINE_WIDTH)
+                    .line_style(LineDrawStyle::Dashed)
+                    .opacity(opacity);
+                self.line(from, to, Some(outline_line_style));
+            }
+            RecordedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } => {
+                let outline_rect_style = RectStyle::default()
+                    .fill("transparent")
+                    .stroke(&color)
+                    .stroke_width(OUTLINE_WIDTH)
+                    .line_style(LineDrawStyle::Dashed)
+                    .opacity(opacity);
+                self.rect(x, y, width, height, Some(outline_rect_style));
+            }
+            RecordedVisual::Poly { points, .. } => {
+                let outline_poly_style = PolyStyle::default()
+                    .fill("transparent")
+                    .stroke(&color)
+                    .stroke_width(OUTLINE_WIDTH)
+                    .line_style(LineDrawStyle::Dashed)
+                    .opacity(opacity);
+                self.poly(points, Some(outline_poly_style));
+            }
+            RecordedVisual::Text { .. } => {}
+        }
+        self.site_outline = Some(color);
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{DetailLevel, DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, PolyStyle, StructureType};
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::rc::Rc;
+
+/// Highest room controller level.
+const MAX_LEVEL: u8 = 8;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a controller of given level, 0 to 8, with eight level segments around it, like in the
+    /// game. The optional progress, 0.0 to 1.0, of upgrading to the next level is drawn as an arc in
+    /// the middle.
+    pub fn controller(&mut self, x: f32, y: f32, level: u8, progress: Option<f32>, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_controller(x, y, level, progress, opacity)
+        });
+    }
+
+    fn draw_controller(&mut self, x: f32, y: f32, level: u8, progress: Option<f32>, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.outline_color();
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            self.minimal_structure(x, y, StructureType::Controller, opacity);
+            return;
+        }
+
+        let outer_circle_style = CircleStyle::default()
+            .radius(0.8)
+            .fill(&theme.dark)
+            .stroke(&outline)
+            .stroke_width(0.05)
+            .opacity(opacity);
+        self.circle(x, y, Some(outer_circle_style));
+
+        let level_ring_style = CircleStyle::default()
+            .radius(0.55)
+            .fill("transparent")
+            .stroke(&theme.gray)
+            .stroke_width(0.3)
+            .opacity(opacity);
+        self.circle(x, y, Some(level_ring_style));
+
+        for segment in 0..level.min(MAX_LEVEL) {
+            // Segments start at the top and go clockwise, with small gaps between them.
+            let start_angle = -FRAC_PI_2 + segment as f32 * PI / 4.0 + 0.06;
+            let end_angle = start_angle + PI / 4.0 - 0.12;
+            let mut segment_points = arc_points(x, y, 0.7, start_angle, end_angle, 3);
+            segment_points.extend(arc_points(x, y, 0.4, end_angle, start_angle, 3));
+            segment_points.push(segment_points[0]);
+            let segment_poly_style = PolyStyle::default()
+                .fill(&theme.light)
+                .stroke("transparent")
+                .opacity(opacity);
+            self.poly(segment_points, Some(segment_poly_style));
+        }
+
+        let inner_circle_style = CircleStyle::default()
+            .radius(0.3)
+            .fill(&theme.dark)
+            .opacity(opacity);
+        self.circle(x, y, Some(inner_circle_style));
+
+        if let Some(progress) = progress.filter(|&progress| progress > 0.0) {
+            let progress = progress.min(1.0);
+            let end_angle = -FRAC_PI_2 + progress * 2.0 * PI;
+            let arc_segments = (progress * 16.0).ceil() as usize;
+            let progress_poly_style = PolyStyle::default()
+                .fill("transparent")
+                .stroke(&theme.energy)
+                .stroke_width(0.1)
+                .opacity(opacity);
+            self.poly(
+                arc_points(x, y, 0.2, -FRAC_PI_2, end_angle, arc_segments + 1),
+                Some(progress_poly_style),
+            );
+        }
+    }
+}
+
+/// Evenly spaced points of an arc of given radius from one angle to another, inclusive.
+pub(crate) fn arc_points(
+    x: f32,
+    y: f32,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    count: usize,
+) -> Vec<(f32, f32)> {
+    (0..count)
+        .map(|i| {
+            let angle =
+                start_angle + (end_angle - start_angle) * i as f32 / (count - 1).max(1) as f32;
+            (x + radius * angle.cos(), y + radius * angle.sin())
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::regions::ROOM_SIZE;
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::{RoomXY, StructureType};
+use std::error::Error;
+use std::fmt;
+
+/// Coordinates that do not point to a tile in the room.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutOfRoomError {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl fmt::Display for OutOfRoomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "coordinates ({}, {}) are outside of the room",
+            self.x, self.y
+        )
+    }
+}
+
+impl Error for OutOfRoomError {}
+
+/// Tile containing the point with given coordinates, i.e., the one with the nearest center.
+pub fn room_xy(x: f32, y: f32) -> Result<RoomXY, OutOfRoomError> {
+    let to_coordinate = |value: f32| {
+        let value = value.round();
+        (value >= 0.0 && value < ROOM_SIZE as f32).then_some(value as u8)
+    };
+    to_coordinate(x)
+        .zip(to_coordinate(y))
+        .and_then(|xy| RoomXY::try_from(xy).ok())
+        .ok_or(OutOfRoomError { x, y })
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Same as `structure`, but returns an error instead of drawing if the coordinates are outside
+    /// of the room.
+    pub fn try_structure(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        opacity: f32,
+    ) -> Result<(), OutOfRoomError> {
+        room_xy(x, y)?;
+        self.structure(x, y, structure_type, opacity);
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::controller::arc_points;
+use crate::{DetailLevel, RoomVisualBackend, RoomVisualExt, Theme};
+use screeps::{CircleStyle, Part, PolyStyle, PowerCreepClass};
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::rc::Rc;
+
+/// Maximum number of body parts of a creep. A ring of that many parts covers the full circle.
+const MAX_CREEP_SIZE: usize = 50;
+
+/// Order of the parts on the front of a creep, from the center outwards.
+const FRONT_PARTS: [Part; 6] = [
+    Part::Claim,
+    Part::Heal,
+    Part::RangedAttack,
+    Part::Attack,
+    Part::Work,
+    Part::Carry,
+];
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a creep with given body facing up, like in the game client: a dark body circle with
+    /// arcs of the parts' colors around it, move parts at the back and other parts at the front,
+    /// each part taking 1/50 of the circle. Tough parts are drawn as a gray circle behind the body.
+    pub fn creep(&mut self, x: f32, y: f32, body: &[Part], opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let minimal = self.effective_detail_level() == DetailLevel::Minimal;
+
+        if !minimal && body.contains(&Part::Tough) {
+            let tough_circle_style = CircleStyle::default()
+                .radius(0.55)
+                .fill(&theme.tough_part)
+                .opacity(opacity);
+            self.circle(x, y, Some(tough_circle_style));
+        }
+
+        let body_circle_style = CircleStyle::default()
+            .radius(0.45)
+            .fill(&theme.dark)
+            .opacity(opacity);
+        self.circle(x, y, Some(body_circle_style));
+
+        if minimal {
+            return;
+        }
+
+        let part_angle = 2.0 * PI / MAX_CREEP_SIZE as f32;
+        let count_parts = |part| body.iter().filter(|&&body_part| body_part == part).count();
+
+        let move_parts = count_parts(Part::Move);
+        if move_parts > 0 {
+            let half_span = move_parts as f32 * part_angle / 2.0;
+            self.part_arc(
+                x,
+                y,
+                FRAC_PI_2 - half_span,
+                FRAC_PI_2 + half_span,
+                Part::Move,
+                opacity,
+            );
+        }
+
+        let front_parts = FRONT_PARTS.map(count_parts);
+        let mut start_angle =
+            -FRAC_PI_2 - front_parts.iter().sum::<usize>() as f32 * part_angle / 2.0;
+        for (part, count) in FRONT_PARTS.into_iter().zip(front_parts) {
+            if count > 0 {
+                let end_angle = start_angle + count as f32 * part_angle;
+                self.part_arc(x, y, start_angle, end_angle, part, opacity);
+                start_angle = end_angle;
+            }
+        }
+    }
+
+    /// Draws a power creep of given class and level, the latter written on it. If given, the ring
+    /// around it has a segment for each power, bright if the power is ready and dark if it is on
+    /// cooldown.
+    pub fn power_creep(
+        &mut self,
+        x: f32,
+        y: f32,
+        class: PowerCreepClass,
+        level: u8,
+        ready_powers: Option<&[bool]>,
+        opacity: f32,
+    ) {
+        let theme = Rc::clone(&self.theme);
+        let class_color = power_creep_class_color(&theme, class);
+
+        let body_circle_style = CircleStyle::default()
+            .radius(0.45)
+            .fill(&theme.dark)
+            .stroke(class_color)
+            .stroke_width(0.08)
+            .opacity(opacity);
+        self.circle(x, y, Some(body_circle_style));
+
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            return;
+        }
+
+        let class_circle_style = CircleStyle::default()
+            .radius(0.25)
+            .fill(class_color)
+            .opacity(opacity);
+        self.circle(x, y, Some(class_circle_style));
+        self.label(x, y, level.to_string(), opacity);
+
+        if let Some(ready_powers) = ready_powers.filter(|powers| !powers.is_empty()) {
+            let power_angle = 2.0 * PI / ready_powers.len() as f32;
+            let gap = power_angle.min(PI / 8.0) * 0.3;
+            for (i, &ready) in ready_powers.iter().enumerate() {
+                let start_angle = -FRAC_PI_2 + i as f32 * power_angle + gap / 2.0;
+                let end_angle = start_angle + power_angle - gap;
+                let arc_segments = ((end_angle - start_angle) / (PI / 8.0)).ceil() as usize;
+                let power_poly_style = PolyStyle::default()
+                    .fill("transparent")
+                    .stroke(if ready { &theme.white } else { &theme.gray })
+                    .stroke_width(0.08)
+                    .opacity(opacity);
+                self.poly(
+                    arc_points(x, y, 0.6, start_angle, end_angle, arc_segments + 1),
+                    Some(power_poly_style),
+                );
+            }
+        }
+    }
+
+    fn part_arc(
+        &mut self,
+        x: f32,
+        y: f32,
+        start_angle: f32,
+        end_angle: f32,
+        part: Part,
+        opacity: f32,
+    ) {
+        let arc_segments = ((end_angle - start_angle) / (PI / 8.0)).ceil() as usize;
+        let part_poly_style = PolyStyle::default()
+            .fill("transparent")
+            .stroke(part_color(&self.theme, part))
+            .stroke_width(0.1)
+            .opacity(opacity);
+        self.poly(
+            arc_points(x, y, 0.4, start_angle, end_angle, arc_segments + 1),
+            Some(part_poly_style),
+        );
+    }
+}
+
+fn power_creep_class_color(theme: &Theme, class: PowerCreepClass) -> &str {
+    match class {
+        PowerCreepClass::Operator => &theme.power,
+        _ => &theme.light,
+    }
+}
+
+fn part_color(theme: &Theme, part: Part) -> &str {
+    match part {
+        Part::Move => &theme.move_part,
+        Part::Work => &theme.work_part,
+        Part::Carry => &theme.carry_part,
+        Part::Attack => &theme.attack_part,
+        Part::RangedAttack => &theme.ranged_attack_part,
+        Part::Heal => &theme.heal_part,
+        Part::Claim => &theme.claim_part,
+        _ => &theme.tough_part,
+    }
+}
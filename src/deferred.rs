@@ -0,0 +1,203 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, LineStyle, PolyStyle, RectStyle, StructureType, TextStyle};
+use std::borrow::Cow;
+
+/// A named layer of deferred draws. Layers with lower priority are drawn first, i.e., below the
+/// ones with higher priority.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DrawLayer {
+    name: Cow<'static, str>,
+    priority: i32,
+}
+
+impl DrawLayer {
+    pub const TERRAIN: DrawLayer = DrawLayer::with_static_name("terrain", 0);
+    pub const ROADS: DrawLayer = DrawLayer::with_static_name("roads", 100);
+    pub const STRUCTURES: DrawLayer = DrawLayer::with_static_name("structures", 200);
+    pub const RAMPARTS: DrawLayer = DrawLayer::with_static_name("ramparts", 300);
+    pub const OVERLAYS: DrawLayer = DrawLayer::with_static_name("overlays", 400);
+    pub const HUD: DrawLayer = DrawLayer::with_static_name("hud", 500);
+
+    /// Creates a custom layer, e.g., `DrawLayer::new("paths", 150)` to draw paths above roads and
+    /// below structures.
+    pub fn new(name: &str, priority: i32) -> Self {
+        DrawLayer {
+            name: Cow::Owned(name.to_string()),
+            priority,
+        }
+    }
+
+    const fn with_static_name(name: &'static str, priority: i32) -> Self {
+        DrawLayer {
+            name: Cow::Borrowed(name),
+            priority,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+/// Arguments of a primitive draw waiting in the queue.
+#[derive(Clone)]
+pub(crate) enum QueuedVisual {
+    Circle {
+        x: f32,
+        y: f32,
+        style: Option<CircleStyle>,
+    },
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        style: Option<LineStyle>,
+    },
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        style: Option<RectStyle>,
+    },
+    Poly {
+        points: Vec<(f32, f32)>,
+        style: Option<PolyStyle>,
+    },
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        style: Option<TextStyle>,
+    },
+}
+
+impl QueuedVisual {
+    fn draw<B: RoomVisualBackend>(self, backend: &mut B) {
+        match self {
+            QueuedVisual::Circle { x, y, style } => backend.circle(x, y, style),
+            QueuedVisual::Line { from, to, style } => backend.line(from, to, style),
+            QueuedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                style,
+            } => backend.rect(x, y, width, height, style),
+            QueuedVisual::Poly { points, style } => backend.poly(points, style),
+            QueuedVisual::Text { x, y, text, style } => backend.text(x, y, text, style),
+        }
+    }
+}
+
+/// Draws waiting for a flush, together with the layer that subsequent draws go to.
+pub(crate) struct DrawQueue {
+    deferred: bool,
+    layer: DrawLayer,
+    visuals: Vec<(i32, QueuedVisual)>,
+}
+
+impl Default for DrawQueue {
+    fn default() -> Self {
+        DrawQueue {
+            deferred: false,
+            layer: DrawLayer::OVERLAYS,
+            visuals: Vec::new(),
+        }
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Whether draws are queued until `flush` instead of being drawn immediately.
+    pub fn is_deferred(&self) -> bool {
+        self.draw_queue.deferred
+    }
+
+    /// Switches the deferred mode. In the deferred mode, the draws go into the current layer and
+    /// are drawn by `flush`, ordered by the layers' priorities, so that independent parts of the code
+    /// may draw in any order and still get a correct composite. Draws that were not flushed are not
+    /// drawn at all. Switching the deferred mode off does not flush the queued draws.
+    pub fn set_deferred(&mut self, deferred: bool) {
+        self.draw_queue.deferred = deferred;
+    }
+
+    /// Layer of subsequent draws in the deferred mode.
+    pub fn layer(&self) -> &DrawLayer {
+        &self.draw_queue.layer
+    }
+
+    /// Sets the layer of subsequent draws in the deferred mode. Defaults to `DrawLayer::OVERLAYS`.
+    /// Structures and terrain always go into their own layers.
+    pub fn set_layer(&mut self, layer: DrawLayer) {
+        self.draw_queue.layer = layer;
+    }
+
+    /// Number of primitives waiting for `flush`.
+    pub fn queued_len(&self) -> usize {
+        self.draw_queue.visuals.len()
+    }
+
+    /// Draws all queued primitives, layer by layer in the order of priorities and in the order of
+    /// drawing within the layers of the same priority. Nothing is flushed automatically, so call it
+    /// once done drawing in the deferred mode, or use `into_backend`.
+    pub fn flush(&mut self) {
+        let mut visuals = std::mem::take(&mut self.draw_queue.visuals);
+        visuals.sort_by_key(|&(priority, _)| priority);
+        for (_, visual) in visuals {
+            visual.draw(&mut self.room_visual);
+        }
+    }
+
+    /// Flushes the remaining draws and returns the backend, e.g., a `RecordingBackend` with the
+    /// recorded primitives.
+    pub fn into_backend(mut self) -> B {
+        self.flush();
+        self.room_visual
+    }
+
+    /// Draws the primitive immediately or queues it in the current layer in the deferred mode.
+    pub(crate) fn draw_or_queue(&mut self, visual: QueuedVisual) {
+        if self.draw_queue.deferred {
+            let priority = self.draw_queue.layer.priority;
+            self.draw_queue.visuals.push((priority, visual));
+        } else {
+            visual.draw(&mut self.room_visual);
+        }
+    }
+
+    /// Runs the drawing function with subsequent draws going into given layer.
+    pub(crate) fn in_layer<F: FnOnce(&mut Self)>(&mut self, layer: DrawLayer, draw: F) {
+        let previous_layer = std::mem::replace(&mut self.draw_queue.layer, layer);
+        draw(self);
+        self.draw_queue.layer = previous_layer;
+    }
+}
+
+/// Layer of the structure in the deferred mode.
+pub(crate) fn structure_layer(structure_type: StructureType) -> DrawLayer {
+    match structure_type {
+        StructureType::Road => DrawLayer::ROADS,
+        StructureType::Rampart => DrawLayer::RAMPARTS,
+        _ => DrawLayer::STRUCTURES,
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{OverCapPolicy, RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, PolyStyle, RectStyle, StructureType};
+use std::rc::Rc;
+
+/// How detailed the drawn glyphs are.
+///
+/// Cheaper variants use fewer primitives and points, and thus less of the serialized visual data
+/// limit, at the cost of looking less like in the game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DetailLevel {
+    /// Glyphs looking like in the game.
+    Full,
+    /// Glyphs made of at most two primitives without complex polygons.
+    Simplified,
+    /// Glyphs made of a single circle or square in the main colors of the object.
+    Minimal,
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    pub fn detail_level(&self) -> DetailLevel {
+        self.detail_level
+    }
+
+    /// Sets the detail level of subsequently drawn glyphs. Defaults to `DetailLevel::Full`.
+    pub fn set_detail_level(&mut self, detail_level: DetailLevel) {
+        self.detail_level = detail_level;
+    }
+
+    /// Automatically lowers the detail level once given fractions of the size budget are used,
+    /// i.e., of the soft cap or, if it is not set, of the game's limit. For example, with
+    /// `(0.5, 0.9)`, glyphs are simplified after half of the budget is used and minimal after 90%.
+    pub fn set_auto_detail(&mut self, simplified_from: f32, minimal_from: f32) {
+        self.auto_detail_thresholds = Some((simplified_from, minimal_from));
+    }
+
+    pub fn clear_auto_detail(&mut self) {
+        self.auto_detail_thresholds = None;
+    }
+
+    /// Detail level used for the next glyph, taking into account the detail level set, automatic
+    /// detail and `OverCapPolicy::Downgrade`.
+    pub fn effective_detail_level(&self) -> DetailLevel {
+        let auto_detail_level = match self.auto_detail_thresholds {
+            Some((_, minimal_from)) if self.size_budget.used_fraction() >= minimal_from => {
+                DetailLevel::Minimal
+            }
+            Some((simplified_from, _)) if self.size_budget.used_fraction() >= simplified_from => {
+                DetailLevel::Simplified
+            }
+            _ => DetailLevel::Full,
+        };
+        let downgrade_detail_level = if self.size_budget.policy() == OverCapPolicy::Downgrade
+            && self.size_budget.is_over_cap()
+        {
+            DetailLevel::Minimal
+        } else {
+            DetailLevel::Full
+        };
+        self.detail_level
+            .max(auto_detail_level)
+            .max(downgrade_detail_level)
+    }
+
+    /// Draws the simplified variant of given structure. Returns false if the structure has no such
+    /// variant since its full one is already simple.
+    pub(crate) fn simplified_structure(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        opacity: f32,
+    ) -> bool {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.outline_color();
+        match structure_type {
+            StructureType::Wall => {
+                let wall_rect_style = RectStyle::default()
+                    .fill(&theme.dark)
+                    .stroke(&theme.black)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.rect(x - 0.5, y - 0.5, 1.0, 1.0, Some(wall_rect_style));
+            }
+            StructureType::Link => {
+                let link_poly_points = vec![
+                    (0.0, -0.45),
+                    (0.35, 0.0),
+                    (0.0, 0.45),
+                    (-0.35, 0.0),
+                    (0.0, -0.45),
+                ]
+                .into_iter()
+                .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
+                .collect();
+                let link_poly_style = PolyStyle::default()
+                    .fill(&theme.energy)
+                    .stroke(&outline)
+                    .stroke_width(0.1)
+                    .opacity(opacity);
+                self.poly(link_poly_points, Some(link_poly_style));
+            }
+            StructureType::Storage => {
+                let outline_rect_style = RectStyle::default()
+                    .fill(&theme.dark)
+                    .stroke(&outline)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.rect(x - 0.5, y - 0.6, 1.0, 1.2, Some(outline_rect_style));
+
+                let energy_rect_style = RectStyle::default().fill(&theme.energy).opacity(opacity);
+                self.rect(x - 0.35, y - 0.45, 0.7, 0.9, Some(energy_rect_style));
+            }
+            StructureType::Tower => {
+                let outline_circle_style = CircleStyle::default()
+                    .radius(0.6)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.circle(x, y, Some(outline_circle_style));
+
+                let energy_rect_style = RectStyle::default().fill(&theme.energy).opacity(opacity);
+                self.rect(x - 0.4, y - 0.3, 0.8, 0.6, Some(energy_rect_style));
+            }
+            StructureType::Extractor => {
+                let extractor_circle_style = CircleStyle::default()
+                    .radius(0.7)
+                    .fill("transparent")
+                    .stroke(&outline)
+                    .stroke_width(0.2)
+                    .opacity(opacity);
+                self.circle(x, y, Some(extractor_circle_style));
+            }
+            StructureType::Lab => {
+                let outer_circle_style = CircleStyle::default()
+                    .radius(0.5)
+                    .fill(&theme.gray)
+                    .stroke(&outline)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.circle(x, y, Some(outer_circle_style));
+
+                let mineral_circle_style = CircleStyle::default()
+                    .radius(0.2)
+                    .fill(&theme.white)
+                    .opacity(opacity);
+                self.circle(x, y, Some(mineral_circle_style));
+            }
+            StructureType::Terminal => {
+                let outline_rect_style = RectStyle::default()
+                    .fill(&theme.light)
+                    .stroke(&outline)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.rect(x - 0.55, y - 0.55, 1.1, 1.1, Some(outline_rect_style));
+
+                let storage_rect_style = RectStyle::default()
+                    .fill(&theme.white)
+                    .stroke(&theme.gray)
+                    .stroke_width(0.15)
+                    .opacity(opacity);
+                self.rect(x - 0.28, y - 0.28, 0.56, 0.56, Some(storage_rect_style));
+            }
+            StructureType::Nuker => {
+                let outline_poly_points = vec![(0.0, -1.0), (-0.5, 0.5), (0.5, 0.5), (0.0, -1.0)]
+                    .into_iter()
+                    .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
+                    .collect();
+                let outline_poly_style = PolyStyle::default()
+                    .fill(&theme.energy)
+                    .stroke(&outline)
+                    .stroke_width(0.1)
+                    .opacity(opacity);
+                self.poly(outline_poly_points, Some(outline_poly_style));
+            }
+            StructureType::Factory => {
+                let outer_circle_style = CircleStyle::default()
+                    .radius(0.65)
+                    .fill(&theme.factory_outer_circle_fill)
+                    .stroke(&outline)
+                    .stroke_width(0.07)
+                    .opacity(opacity);
+                self.circle(x, y, Some(outer_circle_style));
+
+                let resources_rect_style = RectStyle::default()
+                    .fill(&theme.energy)
+                    .stroke(&theme.white)
+                    .stroke_width(0.12)
+                    .opacity(opacity);
+                self.rect(x - 0.24, y - 0.24, 0.48, 0.48, Some(resources_rect_style));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Draws a single primitive in the main colors of given structure.
+    pub(crate) fn minimal_structure(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        opacity: f32,
+    ) {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.outline_color();
+        let spawn_outline = self.owned_outline_color(&theme.light);
+        let power_spawn_outline = self.owned_outline_color(&theme.power);
+        let rampart_outline = self.owned_outline_color(&theme.rampart_outline);
+        let circle_marker: Option<(f32, &str, &str)> = match structure_type {
+            StructureType::Spawn => Some((0.5, &theme.energy, &spawn_outline)),
+            StructureType::PowerSpawn => Some((0.5, &theme.energy, &power_spawn_outline)),
+            StructureType::Extension => Some((0.35, &theme.energy, &outline)),
+            StructureType::Tower => Some((0.45, &theme.energy, &outline)),
+            StructureType::Observer => Some((0.35, &outline, &theme.dark)),
+            StructureType::Lab => Some((0.4, &theme.gray, &outline)),
+            StructureType::Factory => Some((0.5, &theme.gray, &theme.factory_parts)),
+            StructureType::Extractor => Some((0.6, "transparent", &outline)),
+            StructureType::Road => Some((0.15, &theme.road, "transparent")),
+            StructureType::Controller => Some((0.7, &theme.dark, &theme.light)),
+            StructureType::KeeperLair => Some((0.5, &theme.keeper, &theme.black)),
+            StructureType::Portal => Some((0.5, &theme.portal_fill, &theme.portal)),
+            StructureType::PowerBank => Some((0.5, &theme.power, &theme.dark)),
+            StructureType::InvaderCore => Some((0.55, &theme.invader, &theme.dark)),
+            _ => None,
+        };
+        if let Some((radius, fill, stroke)) = circle_marker {
+            let marker_style = CircleStyle::default()
+                .radius(radius)
+                .fill(fill)
+                .stroke(stroke)
+                .stroke_width(0.1)
+                .opacity(opacity);
+            self.circle(x, y, Some(marker_style));
+            if structure_type == StructureType::Road {
+                self.register_road(x, y, opacity);
+            }
+            return;
+        }
+
+        let rect_marker = match structure_type {
+            StructureType::Wall => Some((0.5, &theme.dark, &theme.black, opacity)),
+            StructureType::Rampart => Some((0.5, &theme.rampart, &rampart_outline, opacity * 0.3)),
+            StructureType::Storage => Some((0.4, &theme.energy, &outline, opacity)),
+            StructureType::Terminal => Some((0.4, &theme.light, &outline, opacity)),
+            StructureType::Nuker => Some((0.4, &theme.energy, &outline, opacity)),
+            StructureType::Link => Some((0.25, &theme.energy, &outline, opacity)),
+            StructureType::Container => Some((0.25, &theme.gray, &theme.dark, opacity)),
+            _ => None,
+        };
+        if let Some((half_size, fill, stroke, opacity)) = rect_marker {
+            let marker_style = RectStyle::default()
+                .fill(fill)
+                .stroke(stroke)
+                .stroke_width(0.07)
+                .opacity(opacity);
+            self.rect(
+                x - half_size,
+                y - half_size,
+                2.0 * half_size,
+                2.0 * half_size,
+                Some(marker_style),
+            );
+        }
+    }
+}
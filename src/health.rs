@@ -0,0 +1,108 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::label::compact_hits;
+use crate::theme::blend;
+use crate::{DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{RectStyle, StructureType};
+use std::rc::Rc;
+
+const HEALTH_BAR_WIDTH: f32 = 0.9;
+const HEALTH_BAR_HEIGHT: f32 = 0.12;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a structure like `structure` together with its health. Ramparts and walls, which have
+    /// millions of hits, get their absolute hits written below them, see `hits_amount`, and other
+    /// structures get a health bar, see `health_bar`.
+    pub fn structure_with_health(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        hits: u32,
+        hits_max: u32,
+        opacity: f32,
+    ) {
+        self.structure(x, y, structure_type, opacity);
+        match structure_type {
+            StructureType::Rampart | StructureType::Wall => self.hits_amount(x, y, hits, opacity),
+            _ => self.health_bar(x, y, hits, hits_max, opacity),
+        }
+    }
+
+    /// Draws a bar at the bottom of the tile, filled in the proportion of the hits to the maximum
+    /// hits with a color from `Theme::health_high` through `health_medium` to `health_low`.
+    ///
+    /// In the deferred mode, the bar goes into the overlays layer, above all structures.
+    pub fn health_bar(&mut self, x: f32, y: f32, hits: u32, hits_max: u32, opacity: f32) {
+        self.in_layer(DrawLayer::OVERLAYS, |ext| {
+            ext.draw_health_bar(x, y, hits, hits_max, opacity)
+        });
+    }
+
+    /// Writes the hits in a compact form, e.g., 12.3M, at the bottom of the tile.
+    ///
+    /// In the deferred mode, the text goes into the overlays layer, above all structures.
+    pub fn hits_amount(&mut self, x: f32, y: f32, hits: u32, opacity: f32) {
+        self.in_layer(DrawLayer::OVERLAYS, |ext| {
+            ext.label(x, y + 0.35, compact_hits(hits), opacity)
+        });
+    }
+
+    fn draw_health_bar(&mut self, x: f32, y: f32, hits: u32, hits_max: u32, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let fraction = if hits_max == 0 {
+            0.0
+        } else {
+            (hits as f32 / hits_max as f32).min(1.0)
+        };
+        let (left, top) = (x - HEALTH_BAR_WIDTH / 2.0, y + 0.5 - HEALTH_BAR_HEIGHT);
+
+        let background_rect_style = RectStyle::default()
+            .fill(&theme.dark)
+            .stroke(&theme.black)
+            .stroke_width(0.03)
+            .opacity(opacity);
+        self.rect(
+            left,
+            top,
+            HEALTH_BAR_WIDTH,
+            HEALTH_BAR_HEIGHT,
+            Some(background_rect_style),
+        );
+
+        if fraction > 0.0 {
+            let color = if fraction >= 0.5 {
+                blend(
+                    &theme.health_medium,
+                    &theme.health_high,
+                    fraction * 2.0 - 1.0,
+                )
+            } else {
+                blend(&theme.health_low, &theme.health_medium, fraction * 2.0)
+            };
+            let health_rect_style = RectStyle::default().fill(&color).opacity(opacity);
+            self.rect(
+                left,
+                top,
+                HEALTH_BAR_WIDTH * fraction,
+                HEALTH_BAR_HEIGHT,
+                Some(health_rect_style),
+            );
+        }
+    }
+}
@@ -0,0 +1,686 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Value as it would be seen by `JSON.stringify`. The `screeps` styles and visuals only expose their
+/// properties through `Serialize`, so they are inspected in this form outside of the game.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Value of the property of an object. `None` for other values.
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f32(&self) -> Option<f32> {
+        match self {
+            JsonValue::Number(number) => Some(*number as f32),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Length of the value serialized by `JSON.stringify`. The `f32` numbers are widened to `f64`,
+    /// so they have as many digits as in JS.
+    pub(crate) fn js_len(&self) -> usize {
+        match self {
+            JsonValue::Null => 4,
+            JsonValue::Bool(true) => 4,
+            JsonValue::Bool(false) => 5,
+            JsonValue::Number(number) => js_number_len(*number),
+            JsonValue::String(string) => js_string_len(string),
+            JsonValue::Array(values) => {
+                2 + values.iter().map(JsonValue::js_len).sum::<usize>()
+                    + values.len().saturating_sub(1)
+            }
+            JsonValue::Object(entries) => {
+                2 + entries
+                    .iter()
+                    .map(|(key, value)| js_string_len(key) + 1 + value.js_len())
+                    .sum::<usize>()
+                    + entries.len().saturating_sub(1)
+            }
+        }
+    }
+}
+
+/// Length of a number written by JS, which writes integral numbers without the fractional part and
+/// uses the exponent notation for very small and very large numbers.
+fn js_number_len(number: f64) -> usize {
+    if !number.is_finite() {
+        // Written as `null`.
+        4
+    } else if number.fract() == 0.0 && number.abs() < 1e21 {
+        // Written without the sign of a negative zero.
+        format!("{:.0}", number.abs()).len() + usize::from(number < 0.0)
+    } else if number != 0.0 && number.abs() < 1e-6 {
+        format!("{number:e}").len()
+    } else if number.abs() >= 1e21 {
+        // JS writes the sign of the exponent, e.g., `1e+21`.
+        format!("{number:e}").len() + 1
+    } else {
+        number.to_string().len()
+    }
+}
+
+fn js_string_len(string: &str) -> usize {
+    2 + string
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' | '\n' | '\r' | '\t' | '\u{8}' | '\u{c}' => 2,
+            c if (c as u32) < 0x20 => 6,
+            c => c.len_utf8(),
+        })
+        .sum::<usize>()
+}
+
+/// Serializes the value into a `JsonValue` the same way a JSON serializer would.
+pub(crate) fn to_json_value<T: Serialize + ?Sized>(value: &T) -> Result<JsonValue, JsonError> {
+    value.serialize(JsonValueSerializer)
+}
+
+#[derive(Debug)]
+pub(crate) struct JsonError(String);
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+impl ser::Error for JsonError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        JsonError(msg.to_string())
+    }
+}
+
+struct JsonValueSerializer;
+
+impl ser::Serializer for JsonValueSerializer {
+    type Ok = JsonValue;
+    type Error = JsonError;
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeVariant<SerializeObject>;
+
+    fn serialize_bool(self, v: bool) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JsonValue, JsonError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Number(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Array(
+            v.iter()
+                .map(|&byte| JsonValue::Number(byte.into()))
+                .collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<JsonValue, JsonError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JsonValue, JsonError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<JsonValue, JsonError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Object(vec![(
+            variant.to_owned(),
+            to_json_value(value)?,
+        )]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, JsonError> {
+        Ok(SerializeArray(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, JsonError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, JsonError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, JsonError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject, JsonError> {
+        Ok(SerializeObject {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject, JsonError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeObject>, JsonError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SerializeArray(Vec<JsonValue>);
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonError> {
+        self.0.push(to_json_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Array(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeObject {
+    entries: Vec<(String, JsonValue)>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), JsonError> {
+        let key = match to_json_value(key)? {
+            JsonValue::String(key) => key,
+            JsonValue::Number(key) => key.to_string(),
+            JsonValue::Bool(key) => key.to_string(),
+            _ => return Err(ser::Error::custom("key must be a string")),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("value serialized before its key"))?;
+        self.entries.push((key, to_json_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Object(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), JsonError> {
+        self.entries.push((key.to_owned(), to_json_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Enum variant with data, serialized as an object with a single property named after the variant.
+struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Object(vec![(
+            self.variant.to_owned(),
+            ser::SerializeSeq::end(self.inner)?,
+        )]))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject> {
+    type Ok = JsonValue;
+    type Error = JsonError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), JsonError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Object(vec![(
+            self.variant.to_owned(),
+            ser::SerializeMap::end(self.inner)?,
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use screeps::{
+        CircleStyle, LineDrawStyle, LineStyle, PolyStyle, RectStyle, TextAlign, TextStyle, Visual,
+    };
+    use std::collections::BTreeMap;
+
+    fn number(number: f64) -> JsonValue {
+        JsonValue::Number(number)
+    }
+
+    fn string(string: &str) -> JsonValue {
+        JsonValue::String(string.to_string())
+    }
+
+    fn object(entries: &[(&str, JsonValue)]) -> JsonValue {
+        JsonValue::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Asserts the value of the serialized style or visual and that its length is the same as the
+    /// length of the given output of `JSON.stringify`.
+    fn assert_json<T: Serialize>(value: &T, expected: JsonValue, stringified: &str) {
+        let value = to_json_value(value).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(value.js_len(), stringified.len(), "{stringified}");
+    }
+
+    #[test]
+    fn circle_style() {
+        let style = CircleStyle::default()
+            .radius(0.1)
+            .fill("#555555")
+            .opacity(0.5)
+            .stroke("#ffffff")
+            .stroke_width(0.05);
+        assert_json(
+            &style,
+            object(&[
+                ("radius", number(0.1f32 as f64)),
+                ("fill", string("#555555")),
+                ("opacity", number(0.5)),
+                ("stroke", string("#ffffff")),
+                ("strokeWidth", number(0.05f32 as f64)),
+            ]),
+            r##"{"radius":0.10000000149011612,"fill":"#555555","opacity":0.5,"stroke":"#ffffff","strokeWidth":0.05000000074505806}"##,
+        );
+        assert_json(&CircleStyle::default(), object(&[]), "{}");
+    }
+
+    #[test]
+    fn line_style() {
+        let style = LineStyle::default()
+            .width(1.0)
+            .color("#8fbb93")
+            .opacity(0.25)
+            .line_style(LineDrawStyle::Dashed);
+        assert_json(
+            &style,
+            object(&[
+                ("width", number(1.0)),
+                ("color", string("#8fbb93")),
+                ("opacity", number(0.25)),
+                ("lineStyle", string("dashed")),
+            ]),
+            r##"{"width":1,"color":"#8fbb93","opacity":0.25,"lineStyle":"dashed"}"##,
+        );
+        // The solid line style is the default and is skipped.
+        assert_json(
+            &LineStyle::default().line_style(LineDrawStyle::Solid),
+            object(&[]),
+            "{}",
+        );
+    }
+
+    #[test]
+    fn rect_style() {
+        let style = RectStyle::default()
+            .fill("transparent")
+            .stroke("#181818")
+            .stroke_width(0.3)
+            .line_style(LineDrawStyle::Dotted);
+        assert_json(
+            &style,
+            object(&[
+                ("fill", string("transparent")),
+                ("stroke", string("#181818")),
+                ("strokeWidth", number(0.3f32 as f64)),
+                ("lineStyle", string("dotted")),
+            ]),
+            r##"{"fill":"transparent","stroke":"#181818","strokeWidth":0.30000001192092896,"lineStyle":"dotted"}"##,
+        );
+    }
+
+    #[test]
+    fn poly_style() {
+        let style = PolyStyle::default().fill("#ffe56d").opacity(1.0);
+        assert_json(
+            &style,
+            object(&[("fill", string("#ffe56d")), ("opacity", number(1.0))]),
+            r##"{"fill":"#ffe56d","opacity":1}"##,
+        );
+    }
+
+    #[test]
+    fn text_style() {
+        let style = TextStyle::default()
+            .color("#ffffff")
+            .font(0.5)
+            .stroke("#000000")
+            .background_color("#181818")
+            .align(TextAlign::Left);
+        assert_json(
+            &style,
+            object(&[
+                ("color", string("#ffffff")),
+                ("font", number(0.5)),
+                ("stroke", string("#000000")),
+                ("backgroundColor", string("#181818")),
+                ("align", string("left")),
+            ]),
+            r##"{"color":"#ffffff","font":0.5,"stroke":"#000000","backgroundColor":"#181818","align":"left"}"##,
+        );
+        assert_json(
+            &TextStyle::default().custom_font("0.7 \"Courier New\""),
+            object(&[("font", string("0.7 \"Courier New\""))]),
+            r#"{"font":"0.7 \"Courier New\""}"#,
+        );
+    }
+
+    #[test]
+    fn visuals() {
+        assert_json(
+            &Visual::circle(10.0, 12.5, Some(CircleStyle::default().radius(0.25))),
+            object(&[
+                ("t", string("c")),
+                ("x", number(10.0)),
+                ("y", number(12.5)),
+                ("s", object(&[("radius", number(0.25))])),
+            ]),
+            r#"{"t":"c","x":10,"y":12.5,"s":{"radius":0.25}}"#,
+        );
+        assert_json(
+            &Visual::poly(vec![(1.0, 2.0), (3.5, 4.0)], None),
+            object(&[
+                ("t", string("p")),
+                (
+                    "points",
+                    JsonValue::Array(vec![
+                        JsonValue::Array(vec![number(1.0), number(2.0)]),
+                        JsonValue::Array(vec![number(3.5), number(4.0)]),
+                    ]),
+                ),
+            ]),
+            r#"{"t":"p","points":[[1,2],[3.5,4]]}"#,
+        );
+        assert_json(
+            &Visual::text(1.0, 2.0, "4.5k".to_string(), None),
+            object(&[
+                ("t", string("t")),
+                ("text", string("4.5k")),
+                ("x", number(1.0)),
+                ("y", number(2.0)),
+            ]),
+            r#"{"t":"t","text":"4.5k","x":1,"y":2}"#,
+        );
+    }
+
+    #[test]
+    fn nested_values() {
+        let mut map = BTreeMap::new();
+        map.insert("a", (vec![1, 2], None::<bool>));
+        map.insert("b", (vec![], Some(true)));
+        assert_json(
+            &map,
+            object(&[
+                (
+                    "a",
+                    JsonValue::Array(vec![
+                        JsonValue::Array(vec![number(1.0), number(2.0)]),
+                        JsonValue::Null,
+                    ]),
+                ),
+                (
+                    "b",
+                    JsonValue::Array(vec![JsonValue::Array(vec![]), JsonValue::Bool(true)]),
+                ),
+            ]),
+            r#"{"a":[[1,2],null],"b":[[],true]}"#,
+        );
+        let nested = object(&[("a", object(&[("b", object(&[("c", string("d"))]))]))]);
+        assert_eq!(nested.js_len(), r#"{"a":{"b":{"c":"d"}}}"#.len());
+    }
+
+    #[test]
+    fn number_lengths() {
+        for (value, stringified) in [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (25.0, "25"),
+            (-3.0, "-3"),
+            (4294967295.0, "4294967295"),
+            (1e20, "100000000000000000000"),
+            (1.5, "1.5"),
+            (-0.25, "-0.25"),
+            (0.1f32 as f64, "0.10000000149011612"),
+            (0.7f32 as f64, "0.699999988079071"),
+            (0.000001, "0.000001"),
+            (1e-7, "1e-7"),
+            (-1.5e-7, "-1.5e-7"),
+            (1e21, "1e+21"),
+            (1.5e300, "1.5e+300"),
+            (f64::NAN, "null"),
+            (f64::INFINITY, "null"),
+        ] {
+            assert_eq!(js_number_len(value), stringified.len(), "{stringified}");
+        }
+    }
+
+    #[test]
+    fn string_lengths() {
+        for (value, stringified) in [
+            ("", r#""""#),
+            ("#ffffff", r##""#ffffff""##),
+            ("a\"b\\c", r#""a\"b\\c""#),
+            ("line\nbreak\ttab\r", r#""line\nbreak\ttab\r""#),
+            ("\u{8}\u{c}", r#""\b\f""#),
+            ("\u{1}\u{1f}", r#""\u0001\u001f""#),
+            ("é", "\"é\""),
+        ] {
+            assert_eq!(js_string_len(value), stringified.len(), "{stringified}");
+        }
+    }
+}
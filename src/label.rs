@@ -0,0 +1,66 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::TextStyle;
+use std::rc::Rc;
+
+const LABEL_FONT_SIZE: f32 = 0.35;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a small text vertically centered at (x, y), e.g., an amount on top of a glyph.
+    pub(crate) fn label(&mut self, x: f32, y: f32, text: String, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        // No stroke, since `TextStyle::stroke_width` sets the opacity instead and the default stroke
+        // width is too thick for small texts.
+        let label_style = TextStyle::default()
+            .color(&theme.white)
+            .font(LABEL_FONT_SIZE)
+            .opacity(opacity);
+        self.text(x, y + 0.35 * LABEL_FONT_SIZE, text, Some(label_style));
+    }
+}
+
+/// Formats an amount in at most four characters below a billion, e.g., 950, 4.5k, 12k or 4M, and
+/// in five above, e.g., 1000M. The amount is rounded down, so that it is never overstated, e.g.,
+/// 999,999 is 999k rather than 1000k.
+pub(crate) fn compact_amount(amount: u32) -> String {
+    compact_number(amount, 10.0)
+}
+
+/// Formats hit points in at most five characters, keeping a decimal digit of up to 99.9 thousands
+/// or millions, e.g., 950, 12.3k or 12.3M. The hit points are rounded down like in
+/// `compact_amount`.
+pub(crate) fn compact_hits(hits: u32) -> String {
+    compact_number(hits, 100.0)
+}
+
+/// Formats a number in thousands or millions, with a single decimal digit below given value. The
+/// number is rounded down. Millions are used for all larger numbers, so billions take up to five
+/// digits, e.g., 4294M.
+fn compact_number(number: u32, fraction_below: f64) -> String {
+    let (value, suffix) = match number {
+        0..=999 => return number.to_string(),
+        1000..=999_999 => (number as f64 / 1e3, "k"),
+        _ => (number as f64 / 1e6, "M"),
+    };
+    if value < fraction_below {
+        format!("{}{suffix}", (value * 10.0).floor() / 10.0)
+    } else {
+        format!("{}{suffix}", value.floor())
+    }
+}
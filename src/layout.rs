@@ -0,0 +1,47 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::{RoomXY, StructureType};
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a whole layout of structures given in any order, e.g., a plan loaded from storage.
+    ///
+    /// The structures are drawn in the order that gives game-like results: roads first, then most
+    /// structures, then containers that may stand on roads and finally ramparts on top. The order of
+    /// structures within a layer is preserved.
+    pub fn structures<I>(&mut self, layout: I, opacity: f32)
+    where
+        I: IntoIterator<Item = (RoomXY, StructureType)>,
+    {
+        let mut layout = layout.into_iter().collect::<Vec<_>>();
+        layout.sort_by_key(|&(_, structure_type)| structure_z_order(structure_type));
+        for (xy, structure_type) in layout {
+            self.structure_roomxy(xy, structure_type, opacity);
+        }
+    }
+}
+
+/// Position of the structure type in the drawing order, lower first.
+pub(crate) fn structure_z_order(structure_type: StructureType) -> u8 {
+    match structure_type {
+        StructureType::Road => 0,
+        StructureType::Container => 2,
+        StructureType::Rampart => 3,
+        _ => 1,
+    }
+}
@@ -15,46 +15,204 @@
 // DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+mod animated;
+mod backend;
+mod budget;
+mod construction;
+mod controller;
+mod coordinates;
+mod creep;
+mod deferred;
+mod detail;
+mod health;
+mod json;
+mod label;
+mod layout;
+mod neutral;
+mod ownership;
+mod ramparts;
+mod raster;
+mod recording;
+mod regions;
+mod resource;
+mod roads;
+mod room_objects;
+mod speech;
+mod svg;
+mod terrain;
+mod theme;
+mod walls;
+
+pub use animated::AnimatedPositionStyle;
+pub use backend::RoomVisualBackend;
+pub use budget::{estimated_size, OverCapPolicy, SizeBudget, ROOM_VISUAL_SIZE_LIMIT};
+pub use coordinates::{room_xy, OutOfRoomError};
+pub use deferred::DrawLayer;
+pub use detail::DetailLevel;
+pub use ownership::Ownership;
+pub use raster::{RasterImage, Rasterizer};
+pub use recording::{RecordedFont, RecordedStyle, RecordedVisual, RecordingBackend};
+pub use speech::SpeechStyle;
+pub use svg::SvgRenderer;
+pub use theme::Theme;
+
+use crate::deferred::{structure_layer, DrawQueue, QueuedVisual};
+use crate::roads::RoadNetwork;
 use screeps::{
-    CircleStyle, LineStyle, PolyStyle, RectStyle, RoomCoordinate, RoomName, RoomVisual,
-    RoomXY, StructureType,
+    CircleStyle, LineStyle, PolyStyle, RectStyle, RoomName, RoomVisual,
+    RoomXY, StructureType, TextStyle, Visual,
 };
-use std::collections::HashSet;
-use std::ops::Deref;
-
-const DARK_COLOR: &str = "#181818";
-const LIGHT_COLOR: &str = "#CCCCCC";
-const OUTLINE_COLOR: &str = "#8FBB93";
-const GRAY_COLOR: &str = "#555555";
-const ENERGY_COLOR: &str = "#FFE87B";
-const POWER_COLOR: &str = "#F53547";
-const ROAD_COLOR: &str = "#666666";
-const WHITE_COLOR: &str = "#FFFFFF";
-const BLACK_COLOR: &str = "#000000";
-const RAMPART_COLOR: &str = "#669966";
-const RAMPART_OUTLINE_COLOR: &str = "#66CC66";
-const FACTORY_OUTER_CIRCLE_FILL_COLOR: &str = "#232323";
-const FACTORY_PARTS_COLOR: &str = "#140a0a";
-const FACTORY_LEVEL_CIRCLE_FILL_COLOR: &str = "#302a2a";
-
-pub struct RoomVisualExt {
-    pub room_visual: RoomVisual,
-    roads: HashSet<RoomXY>,
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// Drawing utilities on top of a `RoomVisualBackend`, by default the in-game `RoomVisual`.
+pub struct RoomVisualExt<B: RoomVisualBackend = RoomVisual> {
+    pub room_visual: B,
+    roads: RoadNetwork,
+    size_budget: SizeBudget,
+    detail_level: DetailLevel,
+    auto_detail_thresholds: Option<(f32, f32)>,
+    draw_queue: DrawQueue,
+    theme: Rc<Theme>,
+    ownership: Ownership,
+    /// Color of the outlines replacing the drawn primitives while drawing the structure of a
+    /// construction site.
+    site_outline: Option<String>,
 }
 
-impl Deref for RoomVisualExt {
-    type Target = RoomVisual;
+impl<B: RoomVisualBackend> Deref for RoomVisualExt<B> {
+    type Target = B;
 
     fn deref(&self) -> &Self::Target {
         &self.room_visual
     }
 }
 
+impl<B: RoomVisualBackend> DerefMut for RoomVisualExt<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.room_visual
+    }
+}
+
 impl RoomVisualExt {
     pub fn new(room_name: RoomName) -> Self {
+        Self::with_backend(RoomVisual::new(Some(room_name)))
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Creates a `RoomVisualExt` drawing into given backend instead of the in-game `RoomVisual`.
+    pub fn with_backend(backend: B) -> Self {
         RoomVisualExt {
-            room_visual: RoomVisual::new(Some(room_name)),
-            roads: HashSet::new(),
+            room_visual: backend,
+            roads: RoadNetwork::default(),
+            size_budget: SizeBudget::default(),
+            detail_level: DetailLevel::Full,
+            auto_detail_thresholds: None,
+            draw_queue: DrawQueue::default(),
+            theme: Rc::new(Theme::game()),
+            ownership: Ownership::Mine,
+            site_outline: None,
+        }
+    }
+
+    /// Estimate of the serialized size of everything drawn through this object so far.
+    pub fn size_budget(&self) -> &SizeBudget {
+        &self.size_budget
+    }
+
+    /// Sets a soft cap on the serialized size of drawn visuals, in bytes, and what to do with draws
+    /// after it is reached. Draws that would exceed the game's 500 kB limit are always rejected.
+    pub fn set_size_cap(&mut self, cap: usize, policy: OverCapPolicy) {
+        self.size_budget.set_cap(Some(cap), policy);
+    }
+
+    pub fn clear_size_cap(&mut self) {
+        self.size_budget.set_cap(None, OverCapPolicy::Reject);
+    }
+
+    /// Attributes subsequent draws to given category in the size budget, e.g., the name of the
+    /// module drawing them.
+    pub fn set_size_category(&mut self, category: &str) {
+        self.size_budget.set_category(category);
+    }
+
+    /// Draws a circle like `RoomVisual::circle` if it fits in the size budget.
+    pub fn circle(&mut self, x: f32, y: f32, style: Option<CircleStyle>) {
+        if self.site_outline.is_some() {
+            let style = RecordedStyle::from_style(style);
+            return self.draw_site_outline(RecordedVisual::Circle { x, y, style });
+        }
+        if self.size_budget.spend(&Visual::circle(x, y, style.clone())) {
+            self.draw_or_queue(QueuedVisual::Circle { x, y, style });
+        }
+    }
+
+    /// Draws a line like `RoomVisual::line` if it fits in the size budget.
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), style: Option<LineStyle>) {
+        if self.site_outline.is_some() {
+            let style = RecordedStyle::from_style(style);
+            return self.draw_site_outline(RecordedVisual::Line { from, to, style });
+        }
+        if self
+            .size_budget
+            .spend(&Visual::line(from, to, style.clone()))
+        {
+            self.draw_or_queue(QueuedVisual::Line { from, to, style });
+        }
+    }
+
+    /// Draws a rectangle like `RoomVisual::rect` if it fits in the size budget.
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, style: Option<RectStyle>) {
+        if self.site_outline.is_some() {
+            let style = RecordedStyle::from_style(style);
+            return self.draw_site_outline(RecordedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                style,
+            });
+        }
+        if self
+            .size_budget
+            .spend(&Visual::rect(x, y, width, height, style.clone()))
+        {
+            self.draw_or_queue(QueuedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                style,
+            });
+        }
+    }
+
+    /// Draws a polyline like `RoomVisual::poly` if it fits in the size budget.
+    pub fn poly(&mut self, points: Vec<(f32, f32)>, style: Option<PolyStyle>) {
+        if self.site_outline.is_some() {
+            let style = RecordedStyle::from_style(style);
+            return self.draw_site_outline(RecordedVisual::Poly { points, style });
+        }
+        if self
+            .size_budget
+            .spend(&Visual::poly(points.clone(), style.clone()))
+        {
+            self.draw_or_queue(QueuedVisual::Poly { points, style });
+        }
+    }
+
+    /// Draws a text like `RoomVisual::text` if it fits in the size budget.
+    pub fn text(&mut self, x: f32, y: f32, text: String, style: Option<TextStyle>) {
+        if self.site_outline.is_some() {
+            // The structures of construction sites have no labels.
+            return;
+        }
+        if self
+            .size_budget
+            .spend(&Visual::text(x, y, text.clone(), style.clone()))
+        {
+            self.draw_or_queue(QueuedVisual::Text { x, y, text, style });
         }
     }
 
@@ -65,94 +223,89 @@ impl RoomVisualExt {
     /// smaller than 1.0 will result in mild visual glitches due to structure's parts visually
     /// overlapping each other instead of the ones in the front hiding the ones behind. However,
     /// everything is still perfectly recognizable even at very low opacities.
-    /// The roads connect to each other automatically.
+    /// The roads connect to each other and to existing roads, see `add_existing_roads`, as soon as
+    /// both ends are known, in the `DrawLayer::ROADS` layer.
+    /// Roads outside of the room are not connected, see `try_structure` to check the coordinates.
     /// For the most game-like results, draw the roads before containers on them, and draw the
-    /// ramparts after structures below them.
+    /// ramparts after structures below them, or draw the whole layout at once with `structures`.
+    /// The structure is drawn in a cheaper variant depending on the detail level, see
+    /// `set_detail_level`.
+    /// In the deferred mode, the structure goes into its layer, see `set_deferred`.
     // Fixing the opacity issue would be be difficult without introducing many more points, which
     // would use up even more of the 500kB serialized visual data limit.
     pub fn structure(&mut self, x: f32, y: f32, structure_type: StructureType, opacity: f32) {
+        self.in_layer(structure_layer(structure_type), |ext| {
+            ext.draw_structure(x, y, structure_type, opacity)
+        });
+    }
+
+    #[allow(clippy::excessive_precision)]
+    fn draw_structure(&mut self, x: f32, y: f32, structure_type: StructureType, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.outline_color();
+        match self.effective_detail_level() {
+            DetailLevel::Full => {}
+            DetailLevel::Simplified => {
+                if self.simplified_structure(x, y, structure_type, opacity) {
+                    return;
+                }
+            }
+            DetailLevel::Minimal => {
+                self.minimal_structure(x, y, structure_type, opacity);
+                return;
+            }
+        }
+
         match structure_type {
             StructureType::Spawn => {
                 let spawn_circle_style = CircleStyle::default()
                     .radius(0.65)
-                    .fill(DARK_COLOR)
-                    .stroke(LIGHT_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&self.owned_outline_color(&theme.light))
                     .stroke_width(0.1)
                     .opacity(opacity);
                 self.circle(x, y, Some(spawn_circle_style));
 
                 let energy_circle_style = CircleStyle::default()
                     .radius(0.4)
-                    .fill(ENERGY_COLOR)
+                    .fill(&theme.energy)
                     .opacity(opacity);
                 self.circle(x, y, Some(energy_circle_style));
             }
             StructureType::Extension => {
                 let extension_circle_style = CircleStyle::default()
                     .radius(0.45)
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.05)
                     .opacity(opacity);
                 self.circle(x, y, Some(extension_circle_style));
 
                 let energy_circle_style = CircleStyle::default()
                     .radius(0.32)
-                    .fill(ENERGY_COLOR)
+                    .fill(&theme.energy)
                     .opacity(opacity);
                 self.circle(x, y, Some(energy_circle_style));
             }
             StructureType::Road => {
                 let road_circle_style = CircleStyle::default()
                     .radius(0.15)
-                    .fill(ROAD_COLOR)
+                    .fill(&theme.road)
                     .opacity(opacity);
                 self.circle(x, y, Some(road_circle_style));
-
-                // Using unsafe as to not introduce error handling for the client just for connecting roads.
-                // Wrong coordinates are not checked in other structures' visualizations anyway.
-                let xy = unsafe {
-                    RoomXY {
-                        x: RoomCoordinate::unchecked_new(x as u8),
-                        y: RoomCoordinate::unchecked_new(y as u8),
-                    }
-                };
-
-                if self.roads.insert(xy) {
-                    for near_y in (y as i16 - 1)..(y as i16 + 2) {
-                        for near_x in (x as i16 - 1)..(x as i16 + 2) {
-                            let near_xy = unsafe {
-                                RoomXY {
-                                    x: RoomCoordinate::unchecked_new(near_x as u8),
-                                    y: RoomCoordinate::unchecked_new(near_y as u8),
-                                }
-                            };
-                            if self.roads.contains(&near_xy) {
-                                let road_line_style = LineStyle::default()
-                                    .width(0.3)
-                                    .color(ROAD_COLOR)
-                                    .opacity(opacity);
-                                self.line(
-                                    (x, y),
-                                    (f32::from(near_x), f32::from(near_y)),
-                                    Some(road_line_style),
-                                );
-                            }
-                        }
-                    }
-                };
+                self.register_road(x, y, opacity);
             }
             StructureType::Wall => {
                 let wall_rect_style = RectStyle::default()
-                    .fill(DARK_COLOR)
-                    .stroke(BLACK_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&theme.black)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.rect(x - 0.5, y - 0.5, 1.0, 1.0, Some(wall_rect_style));
 
                 let wall_stripe_line_style1 = LineStyle::default()
                     .width(0.05)
-                    .color(ROAD_COLOR)
+                    .color(&theme.road)
                     .opacity(opacity);
                 let wall_stripe_line_style2 = wall_stripe_line_style1.clone();
                 self.line(
@@ -168,8 +321,8 @@ impl RoomVisualExt {
             }
             StructureType::Rampart => {
                 let rampart_rect_style = RectStyle::default()
-                    .fill(RAMPART_COLOR)
-                    .stroke(RAMPART_OUTLINE_COLOR)
+                    .fill(&theme.rampart)
+                    .stroke(&self.owned_outline_color(&theme.rampart_outline))
                     .stroke_width(0.07)
                     .opacity(opacity * 0.3);
                 self.rect(x - 0.5, y - 0.5, 1.0, 1.0, Some(rampart_rect_style));
@@ -186,8 +339,8 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let outline_poly_style = PolyStyle::default()
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.06)
                     .opacity(opacity);
                 self.poly(outline_poly_points, Some(outline_poly_style));
@@ -203,7 +356,7 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let interior_poly_style = PolyStyle::default()
-                    .fill(ENERGY_COLOR)
+                    .fill(&theme.energy)
                     .stroke("transparent")
                     .opacity(opacity);
                 self.poly(interior_poly_points, Some(interior_poly_style));
@@ -224,26 +377,26 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let outline_poly_style = PolyStyle::default()
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.poly(outline_poly_points, Some(outline_poly_style));
 
-                let energy_rect_style = RectStyle::default().fill(ENERGY_COLOR).opacity(opacity);
+                let energy_rect_style = RectStyle::default().fill(&theme.energy).opacity(opacity);
                 self.rect(x - 0.35, y - 0.45, 0.7, 0.9, Some(energy_rect_style));
             }
             StructureType::Tower => {
                 let outline_circle_style = CircleStyle::default()
                     .radius(0.6)
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.circle(x, y, Some(outline_circle_style));
 
                 let energy_container_rect_style =
-                    RectStyle::default().fill(ENERGY_COLOR).opacity(opacity);
+                    RectStyle::default().fill(&theme.energy).opacity(opacity);
                 self.rect(
                     x - 0.4,
                     y - 0.3,
@@ -253,8 +406,8 @@ impl RoomVisualExt {
                 );
 
                 let barrel_rect_style = RectStyle::default()
-                    .fill(LIGHT_COLOR)
-                    .stroke(DARK_COLOR)
+                    .fill(&theme.light)
+                    .stroke(&theme.dark)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.rect(x - 0.2, y - 0.9, 0.4, 0.5, Some(barrel_rect_style));
@@ -262,37 +415,37 @@ impl RoomVisualExt {
             StructureType::Observer => {
                 let outer_circle_style = CircleStyle::default()
                     .radius(0.45)
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.05)
                     .opacity(opacity);
                 self.circle(x, y, Some(outer_circle_style));
 
                 let inner_circle_style = CircleStyle::default()
                     .radius(0.2)
-                    .fill(OUTLINE_COLOR)
+                    .fill(&outline)
                     .opacity(opacity);
                 self.circle(x + 0.255, y, Some(inner_circle_style));
             }
             StructureType::PowerSpawn => {
                 let power_spawn_circle_style = CircleStyle::default()
                     .radius(0.65)
-                    .fill(DARK_COLOR)
-                    .stroke(POWER_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&self.owned_outline_color(&theme.power))
                     .stroke_width(0.1)
                     .opacity(opacity);
                 self.circle(x, y, Some(power_spawn_circle_style));
 
                 let power_circle_style = CircleStyle::default()
                     .radius(0.4)
-                    .fill(ENERGY_COLOR)
+                    .fill(&theme.energy)
                     .opacity(opacity);
                 self.circle(x, y, Some(power_circle_style));
             }
             StructureType::Extractor => {
                 let extractor_line_style1 = LineStyle::default()
                     .width(0.2)
-                    .color(OUTLINE_COLOR)
+                    .color(&outline)
                     .opacity(opacity);
                 let extractor_line_style2 = extractor_line_style1.clone();
                 let extractor_line_style3 = extractor_line_style1.clone();
@@ -315,28 +468,28 @@ impl RoomVisualExt {
             StructureType::Lab => {
                 let outer_circle_style = CircleStyle::default()
                     .radius(0.5)
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.circle(x, y - 0.025, Some(outer_circle_style));
 
                 let interior_circle_style = CircleStyle::default()
                     .radius(0.35)
-                    .fill(GRAY_COLOR)
+                    .fill(&theme.gray)
                     .opacity(opacity);
                 self.circle(x, y - 0.025, Some(interior_circle_style));
 
                 let mineral_circle_style = CircleStyle::default()
                     .radius(0.2)
-                    .fill(WHITE_COLOR)
+                    .fill(&theme.white)
                     .opacity(opacity);
                 self.circle(x, y + 0.12, Some(mineral_circle_style));
 
-                let bottom_rect_style = RectStyle::default().fill(DARK_COLOR).opacity(opacity);
+                let bottom_rect_style = RectStyle::default().fill(&theme.dark).opacity(opacity);
                 self.rect(x - 0.45, y + 0.3, 0.9, 0.25, Some(bottom_rect_style));
 
-                let energy_rect_style = RectStyle::default().fill(ENERGY_COLOR).opacity(opacity);
+                let energy_rect_style = RectStyle::default().fill(&theme.energy).opacity(opacity);
                 self.rect(x - 0.2, y + 0.36, 0.4, 0.1, Some(energy_rect_style));
 
                 let bottom_poly_points =
@@ -345,7 +498,7 @@ impl RoomVisualExt {
                         .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                         .collect();
                 let bottom_poly_style = PolyStyle::default()
-                    .stroke(OUTLINE_COLOR)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.poly(bottom_poly_points, Some(bottom_poly_style));
@@ -366,8 +519,8 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let outline_poly_style = PolyStyle::default()
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.poly(outline_poly_points, Some(outline_poly_style));
@@ -387,33 +540,33 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let interior_poly_style = PolyStyle::default()
-                    .fill(LIGHT_COLOR)
+                    .fill(&theme.light)
                     .stroke("transparent")
                     .opacity(opacity);
                 self.poly(interior_poly_points, Some(interior_poly_style));
 
                 let storage_rect_style = RectStyle::default()
-                    .fill(GRAY_COLOR)
-                    .stroke(DARK_COLOR)
+                    .fill(&theme.gray)
+                    .stroke(&theme.dark)
                     .stroke_width(0.1)
                     .opacity(opacity);
                 self.rect(x - 0.36, y - 0.36, 0.72, 0.72, Some(storage_rect_style));
 
                 let mineral_rect_style = RectStyle::default()
-                    .fill(WHITE_COLOR)
+                    .fill(&theme.white)
                     .stroke("transparent")
                     .opacity(opacity);
                 self.rect(x - 0.2, y - 0.2, 0.4, 0.4, Some(mineral_rect_style));
             }
             StructureType::Container => {
                 let container_rect = RectStyle::default()
-                    .fill(GRAY_COLOR)
-                    .stroke(DARK_COLOR)
+                    .fill(&theme.gray)
+                    .stroke(&theme.dark)
                     .stroke_width(0.1)
                     .opacity(opacity);
                 self.rect(x - 0.25, y - 0.3, 0.5, 0.6, Some(container_rect));
 
-                let energy_rect = RectStyle::default().fill(ENERGY_COLOR).opacity(opacity);
+                let energy_rect = RectStyle::default().fill(&theme.energy).opacity(opacity);
                 self.rect(x - 0.192, y + 0.04, 0.39, 0.23, Some(energy_rect));
             }
             StructureType::Nuker => {
@@ -429,8 +582,8 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let outline_poly_style = PolyStyle::default()
-                    .fill(DARK_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.dark)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.poly(outline_poly_points, Some(outline_poly_style));
@@ -441,13 +594,13 @@ impl RoomVisualExt {
                         .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                         .collect();
                 let energy_poly_style = PolyStyle::default()
-                    .fill(ENERGY_COLOR)
-                    .stroke(OUTLINE_COLOR)
+                    .fill(&theme.energy)
+                    .stroke(&outline)
                     .stroke_width(0.01)
                     .opacity(opacity);
                 self.poly(energy_poly_points, Some(energy_poly_style));
 
-                let ghodium_rect_style = RectStyle::default().fill(WHITE_COLOR).opacity(opacity);
+                let ghodium_rect_style = RectStyle::default().fill(&theme.white).opacity(opacity);
                 self.rect(x - 0.35, y + 0.3, 0.7, 0.1, Some(ghodium_rect_style));
             }
             StructureType::Factory => {
@@ -487,7 +640,7 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let outline_style = PolyStyle::default()
-                    .stroke(OUTLINE_COLOR)
+                    .stroke(&outline)
                     .stroke_width(0.07)
                     .opacity(opacity);
                 self.poly(outline_points, Some(outline_style));
@@ -495,8 +648,8 @@ impl RoomVisualExt {
                 // Outer circle.
                 let outer_circle_style = CircleStyle::default()
                     .radius(0.65)
-                    .fill(FACTORY_OUTER_CIRCLE_FILL_COLOR)
-                    .stroke(FACTORY_PARTS_COLOR)
+                    .fill(&theme.factory_outer_circle_fill)
+                    .stroke(&theme.factory_parts)
                     .stroke_width(0.035)
                     .opacity(opacity);
                 self.circle(x, y, Some(outer_circle_style));
@@ -535,8 +688,8 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let spikes_style = PolyStyle::default()
-                    .fill(GRAY_COLOR)
-                    .stroke(FACTORY_PARTS_COLOR)
+                    .fill(&theme.gray)
+                    .stroke(&theme.factory_parts)
                     .stroke_width(0.04)
                     .opacity(opacity);
                 self.poly(spikes_points, Some(spikes_style));
@@ -544,8 +697,8 @@ impl RoomVisualExt {
                 // Factory level circle.
                 let factory_level_circle_style = CircleStyle::default()
                     .radius(0.54)
-                    .fill(FACTORY_LEVEL_CIRCLE_FILL_COLOR)
-                    .stroke(FACTORY_PARTS_COLOR)
+                    .fill(&theme.factory_level_circle_fill)
+                    .stroke(&theme.factory_parts)
                     .stroke_width(0.04)
                     .opacity(opacity);
                 self.circle(x, y, Some(factory_level_circle_style));
@@ -571,7 +724,7 @@ impl RoomVisualExt {
                     .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
                     .collect();
                 let factory_level_gaps_style = PolyStyle::default()
-                    .fill(FACTORY_PARTS_COLOR)
+                    .fill(&theme.factory_parts)
                     .stroke("transparent")
                     .opacity(opacity);
                 self.poly(factory_level_gaps_points, Some(factory_level_gaps_style));
@@ -579,16 +732,21 @@ impl RoomVisualExt {
                 // Inner black circle.
                 let inner_circle_style = CircleStyle::default()
                     .radius(0.42)
-                    .fill(FACTORY_PARTS_COLOR)
+                    .fill(&theme.factory_parts)
                     .opacity(opacity);
                 self.circle(x, y, Some(inner_circle_style));
 
-                let mineral_rect_style = RectStyle::default().fill(WHITE_COLOR).opacity(opacity);
+                let mineral_rect_style = RectStyle::default().fill(&theme.white).opacity(opacity);
                 self.rect(x - 0.24, y - 0.24, 0.48, 0.24, Some(mineral_rect_style));
 
-                let energy_rect_style = RectStyle::default().fill(ENERGY_COLOR).opacity(opacity);
+                let energy_rect_style = RectStyle::default().fill(&theme.energy).opacity(opacity);
                 self.rect(x - 0.24, y, 0.48, 0.24, Some(energy_rect_style));
             }
+            StructureType::Controller => self.controller(x, y, 0, None, opacity),
+            StructureType::KeeperLair => self.keeper_lair(x, y, None, opacity),
+            StructureType::Portal => self.portal(x, y, None, opacity),
+            StructureType::PowerBank => self.power_bank(x, y, None, opacity),
+            StructureType::InvaderCore => self.invader_core(x, y, None, opacity),
             _ => {}
         };
     }
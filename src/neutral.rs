@@ -0,0 +1,180 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::label::compact_amount;
+use crate::{DetailLevel, DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, PolyStyle, StructureType};
+use std::rc::Rc;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a keeper lair. If given, the ticks until the next source keeper spawns are written on
+    /// it.
+    pub fn keeper_lair(&mut self, x: f32, y: f32, ticks_to_spawn: Option<u32>, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_keeper_lair(x, y, ticks_to_spawn, opacity)
+        });
+    }
+
+    fn draw_keeper_lair(&mut self, x: f32, y: f32, ticks_to_spawn: Option<u32>, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            self.minimal_structure(x, y, StructureType::KeeperLair, opacity);
+            return;
+        }
+
+        let outline_poly_points = vec![
+            (-0.23, -0.55),
+            (0.23, -0.55),
+            (0.55, -0.23),
+            (0.55, 0.23),
+            (0.23, 0.55),
+            (-0.23, 0.55),
+            (-0.55, 0.23),
+            (-0.55, -0.23),
+            (-0.23, -0.55),
+        ]
+        .into_iter()
+        .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
+        .collect();
+        let outline_poly_style = PolyStyle::default()
+            .fill(&theme.dark)
+            .stroke(&theme.black)
+            .stroke_width(0.07)
+            .opacity(opacity);
+        self.poly(outline_poly_points, Some(outline_poly_style));
+
+        let keeper_circle_style = CircleStyle::default()
+            .radius(0.3)
+            .fill(&theme.keeper)
+            .opacity(opacity);
+        self.circle(x, y, Some(keeper_circle_style));
+
+        if let Some(ticks_to_spawn) = ticks_to_spawn {
+            self.label(x, y, compact_amount(ticks_to_spawn), opacity);
+        }
+    }
+
+    /// Draws a portal. If given, the ticks until it decays are written on it.
+    pub fn portal(&mut self, x: f32, y: f32, ticks_to_decay: Option<u32>, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_portal(x, y, ticks_to_decay, opacity)
+        });
+    }
+
+    fn draw_portal(&mut self, x: f32, y: f32, ticks_to_decay: Option<u32>, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            self.minimal_structure(x, y, StructureType::Portal, opacity);
+            return;
+        }
+
+        let outer_circle_style = CircleStyle::default()
+            .radius(0.55)
+            .fill(&theme.portal_fill)
+            .stroke(&theme.portal)
+            .stroke_width(0.1)
+            .opacity(opacity);
+        self.circle(x, y, Some(outer_circle_style));
+
+        let inner_circle_style = CircleStyle::default()
+            .radius(0.3)
+            .fill(&theme.portal)
+            .opacity(opacity);
+        self.circle(x, y, Some(inner_circle_style));
+
+        if let Some(ticks_to_decay) = ticks_to_decay {
+            self.label(x, y, compact_amount(ticks_to_decay), opacity);
+        }
+    }
+
+    /// Draws a power bank. If given, the amount of power in it is written on it.
+    pub fn power_bank(&mut self, x: f32, y: f32, power: Option<u32>, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_power_bank(x, y, power, opacity)
+        });
+    }
+
+    fn draw_power_bank(&mut self, x: f32, y: f32, power: Option<u32>, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            self.minimal_structure(x, y, StructureType::PowerBank, opacity);
+            return;
+        }
+
+        let outer_circle_style = CircleStyle::default()
+            .radius(0.6)
+            .fill(&theme.dark)
+            .stroke(&theme.power)
+            .stroke_width(0.1)
+            .opacity(opacity);
+        self.circle(x, y, Some(outer_circle_style));
+
+        let power_circle_style = CircleStyle::default()
+            .radius(0.4)
+            .fill(&theme.power)
+            .opacity(opacity);
+        self.circle(x, y, Some(power_circle_style));
+
+        if let Some(power) = power {
+            self.label(x, y, compact_amount(power), opacity);
+        }
+    }
+
+    /// Draws an invader core. If given, its level is written on it.
+    pub fn invader_core(&mut self, x: f32, y: f32, level: Option<u8>, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_invader_core(x, y, level, opacity)
+        });
+    }
+
+    fn draw_invader_core(&mut self, x: f32, y: f32, level: Option<u8>, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        if self.effective_detail_level() == DetailLevel::Minimal {
+            self.minimal_structure(x, y, StructureType::InvaderCore, opacity);
+            return;
+        }
+
+        let outline_poly_points = vec![
+            (0.0, -0.65),
+            (0.56, -0.325),
+            (0.56, 0.325),
+            (0.0, 0.65),
+            (-0.56, 0.325),
+            (-0.56, -0.325),
+            (0.0, -0.65),
+        ]
+        .into_iter()
+        .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
+        .collect();
+        let outline_poly_style = PolyStyle::default()
+            .fill(&theme.dark)
+            .stroke(&theme.invader)
+            .stroke_width(0.08)
+            .opacity(opacity);
+        self.poly(outline_poly_points, Some(outline_poly_style));
+
+        let core_circle_style = CircleStyle::default()
+            .radius(0.32)
+            .fill(&theme.invader)
+            .opacity(opacity);
+        self.circle(x, y, Some(core_circle_style));
+
+        if let Some(level) = level {
+            self.label(x, y, level.to_string(), opacity);
+        }
+    }
+}
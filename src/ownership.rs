@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt, Theme};
+use screeps::StructureType;
+
+/// Owner of the drawn structures, which determines the color of their outlines.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Ownership {
+    /// Own structures, outlined with `Theme::outline`. The default.
+    #[default]
+    Mine,
+    /// Structures of an ally, outlined with `Theme::ally_outline`.
+    Ally,
+    /// Structures of an enemy, outlined with `Theme::hostile_outline`.
+    Hostile,
+    /// Structures without an owner or of a player that is neither an ally nor an enemy, outlined
+    /// with `Theme::neutral_outline`.
+    Neutral,
+    /// Structures of the player with given username, outlined with their color from
+    /// `Theme::player_outlines` or, if it has none, with `Theme::hostile_outline`.
+    Player(String),
+}
+
+impl Ownership {
+    /// Color of the outlines of structures with this ownership.
+    pub fn outline_color<'a>(&self, theme: &'a Theme) -> &'a str {
+        match self {
+            Ownership::Mine => &theme.outline,
+            Ownership::Ally => &theme.ally_outline,
+            Ownership::Hostile => &theme.hostile_outline,
+            Ownership::Neutral => &theme.neutral_outline,
+            Ownership::Player(username) => theme
+                .player_outlines
+                .get(username)
+                .unwrap_or(&theme.hostile_outline),
+        }
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Ownership of subsequently drawn structures, `Ownership::Mine` by default.
+    pub fn ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    /// Sets the ownership of subsequently drawn structures.
+    pub fn set_ownership(&mut self, ownership: Ownership) {
+        self.ownership = ownership;
+    }
+
+    /// Draws a structure like `structure`, but with outlines colored according to given ownership.
+    pub fn structure_owned(
+        &mut self,
+        x: f32,
+        y: f32,
+        structure_type: StructureType,
+        ownership: Ownership,
+        opacity: f32,
+    ) {
+        let previous_ownership = std::mem::replace(&mut self.ownership, ownership);
+        self.structure(x, y, structure_type, opacity);
+        self.ownership = previous_ownership;
+    }
+
+    /// Color of the outlines of structures with the current ownership.
+    pub(crate) fn outline_color(&self) -> String {
+        self.ownership.outline_color(&self.theme).to_string()
+    }
+
+    /// Color of outlines that have a different color than `Theme::outline` for own structures, e.g.,
+    /// the rings of spawns and power spawns or the outlines of ramparts. Tinted like other outlines
+    /// for other ownerships.
+    pub(crate) fn owned_outline_color(&self, own_color: &str) -> String {
+        match self.ownership {
+            Ownership::Mine => own_color.to_string(),
+            _ => self.outline_color(),
+        }
+    }
+}
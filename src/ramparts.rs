@@ -0,0 +1,66 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::regions::{boundary_loops, merged_rects, ROOM_SIZE};
+use crate::{DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{PolyStyle, RectStyle, RoomXY};
+use std::rc::Rc;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws ramparts on given tiles as merged regions, like the game does. Each region is filled
+    /// without inner borders and outlined only along its perimeter, including around holes.
+    ///
+    /// Uses a few primitives per region instead of one per rampart. In the deferred mode, the
+    /// ramparts go into the ramparts layer.
+    pub fn ramparts<I: IntoIterator<Item = RoomXY>>(&mut self, ramparts: I, opacity: f32) {
+        let mut tiles = [[false; ROOM_SIZE]; ROOM_SIZE];
+        for xy in ramparts {
+            tiles[xy.y.u8() as usize][xy.x.u8() as usize] = true;
+        }
+        self.in_layer(DrawLayer::RAMPARTS, |ext| {
+            ext.draw_ramparts(&tiles, opacity)
+        });
+    }
+
+    fn draw_ramparts(&mut self, tiles: &[[bool; ROOM_SIZE]; ROOM_SIZE], opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let outline = self.owned_outline_color(&theme.rampart_outline);
+        for rect in merged_rects(|x, y| tiles[y][x]) {
+            let rampart_rect_style = RectStyle::default()
+                .fill(&theme.rampart)
+                .opacity(opacity * 0.3);
+            self.rect(
+                rect.x as f32 - 0.5,
+                rect.y as f32 - 0.5,
+                rect.width as f32,
+                rect.height as f32,
+                Some(rampart_rect_style),
+            );
+        }
+        for corners in boundary_loops(|x, y| tiles[y][x]) {
+            let rampart_outline_style = PolyStyle::default()
+                .stroke(&outline)
+                .stroke_width(0.07)
+                .opacity(opacity * 0.3);
+            let points = corners
+                .into_iter()
+                .map(|(x, y)| (x as f32 - 0.5, y as f32 - 0.5))
+                .collect();
+            self.poly(points, Some(rampart_outline_style));
+        }
+    }
+}
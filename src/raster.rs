@@ -0,0 +1,556 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::RecordedVisual;
+use std::io;
+use std::path::Path;
+
+const ROOM_SIZE: u32 = 50;
+const PLAIN_COLOR: &str = "#2B2B2B";
+// Each pixel is sampled SUBSAMPLES x SUBSAMPLES times for antialiasing.
+const SUBSAMPLES: u32 = 4;
+
+/// Renders recorded primitives into an RGBA image of a whole room, 50x50 tiles, on the CPU.
+///
+/// Each primitive is composited like in the game, i.e., its stroke is drawn over its fill and then
+/// the whole primitive is blended over what is below it using its opacity. Texts are not rendered
+/// since no fonts are bundled, though their backgrounds are.
+#[derive(Clone, Debug)]
+pub struct Rasterizer {
+    pixels_per_tile: u32,
+    background: Option<String>,
+}
+
+impl Default for Rasterizer {
+    fn default() -> Self {
+        Rasterizer {
+            pixels_per_tile: 16,
+            background: Some(PLAIN_COLOR.to_string()),
+        }
+    }
+}
+
+impl Rasterizer {
+    pub fn new() -> Self {
+        Rasterizer::default()
+    }
+
+    /// Size of a single tile in the output image. Defaults to 16.
+    pub fn pixels_per_tile(mut self, val: u32) -> Self {
+        self.pixels_per_tile = val.max(1);
+        self
+    }
+
+    /// Color of the room's background. Defaults to the color of plains in the game.
+    pub fn background(mut self, val: &str) -> Self {
+        self.background = Some(val.to_string());
+        self
+    }
+
+    /// Leaves the room's background transparent.
+    pub fn no_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+
+    /// Returns the image with given primitives drawn in order.
+    pub fn render(&self, visuals: &[RecordedVisual]) -> RasterImage {
+        let size = ROOM_SIZE * self.pixels_per_tile;
+        let background = self
+            .background
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or([0.0; 4]);
+        let mut image = RasterImage {
+            width: size,
+            height: size,
+            pixels: vec![background; (size * size) as usize],
+        };
+        for visual in visuals {
+            self.draw(&mut image, visual);
+        }
+        image
+    }
+
+    /// Renders given primitives and writes them to a PNG file.
+    pub fn write_png<P: AsRef<Path>>(&self, visuals: &[RecordedVisual], path: P) -> io::Result<()> {
+        std::fs::write(path, self.render(visuals).encode_png())
+    }
+
+    fn draw(&self, image: &mut RasterImage, visual: &RecordedVisual) {
+        let shape = match Shape::of(visual) {
+            Some(shape) => shape,
+            None => return,
+        };
+        let (fill, stroke) = match visual {
+            // Only the background of a text is rendered.
+            RecordedVisual::Text { style, .. } => (
+                style.background_color.as_deref().and_then(parse_color),
+                None,
+            ),
+            _ => (
+                visual.fill().and_then(parse_color),
+                visual
+                    .stroke()
+                    .and_then(|(color, width)| parse_color(color).map(|color| (color, width))),
+            ),
+        };
+        if fill.is_none() && stroke.is_none() {
+            return;
+        }
+
+        let stroke_width = stroke.map(|(_, width)| width).unwrap_or(0.0);
+        let dash = match visual.style().line_style.as_deref() {
+            Some("dashed") => Some((stroke_width * 4.0, stroke_width * 2.0)),
+            Some("dotted") => Some((stroke_width, stroke_width * 2.0)),
+            _ => None,
+        };
+        let (min_x, min_y, max_x, max_y) = shape.bounds(stroke_width / 2.0);
+        let ppt = self.pixels_per_tile as f32;
+        let to_pixel = |coord: f32| ((coord + 0.5) * ppt).floor();
+        let min_px = to_pixel(min_x).clamp(0.0, image.width as f32) as u32;
+        let min_py = to_pixel(min_y).clamp(0.0, image.height as f32) as u32;
+        let max_px = (to_pixel(max_x) + 1.0).clamp(0.0, image.width as f32) as u32;
+        let max_py = (to_pixel(max_y) + 1.0).clamp(0.0, image.height as f32) as u32;
+        let opacity = visual.opacity().clamp(0.0, 1.0);
+        let samples = (SUBSAMPLES * SUBSAMPLES) as f32;
+
+        for py in min_py..max_py {
+            for px in min_px..max_px {
+                let mut fill_coverage = 0.0;
+                let mut stroke_coverage = 0.0;
+                for sy in 0..SUBSAMPLES {
+                    for sx in 0..SUBSAMPLES {
+                        let x = (px as f32 + (sx as f32 + 0.5) / SUBSAMPLES as f32) / ppt - 0.5;
+                        let y = (py as f32 + (sy as f32 + 0.5) / SUBSAMPLES as f32) / ppt - 0.5;
+                        if stroke.is_some() && shape.on_stroke(x, y, stroke_width, dash) {
+                            stroke_coverage += 1.0;
+                        } else if fill.is_some() && shape.contains(x, y) {
+                            fill_coverage += 1.0;
+                        }
+                    }
+                }
+                if fill_coverage == 0.0 && stroke_coverage == 0.0 {
+                    continue;
+                }
+
+                // Premultiplied color of the primitive in this pixel, stroke over fill.
+                let mut color = [0.0f32; 4];
+                for (coverage, paint) in [
+                    (fill_coverage / samples, fill),
+                    (stroke_coverage / samples, stroke.map(|(color, _)| color)),
+                ] {
+                    if let Some(paint) = paint {
+                        let alpha = paint[3] * coverage;
+                        for channel in 0..3 {
+                            color[channel] += paint[channel] * alpha;
+                        }
+                        color[3] += alpha;
+                    }
+                }
+                image.blend(px, py, color, opacity);
+            }
+        }
+    }
+}
+
+/// RGBA image with channels stored as floats in range [0, 1], not premultiplied.
+#[derive(Clone, Debug)]
+pub struct RasterImage {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl RasterImage {
+    /// Color of given pixel as 8-bit RGBA.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let pixel = self.pixels[(y * self.width + x) as usize];
+        pixel.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Encodes the image as a PNG file with 8-bit RGBA pixels.
+    pub fn encode_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(((self.width * 4 + 1) * self.height) as usize);
+        for y in 0..self.height {
+            // Filter type none.
+            raw.push(0);
+            for x in 0..self.width {
+                raw.extend_from_slice(&self.pixel(x, y));
+            }
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&self.width.to_be_bytes());
+        ihdr.extend_from_slice(&self.height.to_be_bytes());
+        // Bit depth 8, color type RGBA, default compression, filtering and no interlacing.
+        ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        write_chunk(&mut png, b"IHDR", &ihdr);
+        write_chunk(&mut png, b"IDAT", &zlib_compress(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn blend(&mut self, x: u32, y: u32, premultiplied: [f32; 4], opacity: f32) {
+        let dst = &mut self.pixels[(y * self.width + x) as usize];
+        let src_alpha = premultiplied[3] * opacity;
+        let out_alpha = src_alpha + dst[3] * (1.0 - src_alpha);
+        if out_alpha <= 0.0 {
+            return;
+        }
+        for channel in 0..3 {
+            let src = premultiplied[channel] * opacity;
+            dst[channel] = (src + dst[channel] * dst[3] * (1.0 - src_alpha)) / out_alpha;
+        }
+        dst[3] = out_alpha;
+    }
+}
+
+enum Shape {
+    Circle {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    // Polyline, filled as if it was closed when used for a poly.
+    Path {
+        points: Vec<(f32, f32)>,
+        filled: bool,
+    },
+}
+
+impl Shape {
+    fn of(visual: &RecordedVisual) -> Option<Self> {
+        match visual {
+            RecordedVisual::Circle { x, y, .. } => Some(Shape::Circle {
+                x: *x,
+                y: *y,
+                radius: visual.radius(),
+            }),
+            RecordedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } => Some(Shape::Rect {
+                x: *x,
+                y: *y,
+                width: *width,
+                height: *height,
+            }),
+            RecordedVisual::Line { from, to, .. } => Some(Shape::Path {
+                points: vec![*from, *to],
+                filled: false,
+            }),
+            RecordedVisual::Poly { points, .. } => Some(Shape::Path {
+                points: points.clone(),
+                filled: true,
+            }),
+            RecordedVisual::Text { x, y, text, style } => {
+                style.background_color.as_ref()?;
+                // The text's size is approximated in the same way as in SVG.
+                let font_size = visual.font_size();
+                let padding = style.background_padding.unwrap_or(0.3);
+                let width = text.chars().count() as f32 * font_size * 0.6 + 2.0 * padding;
+                let left = match style.align.as_deref() {
+                    Some("left") => *x - padding,
+                    Some("right") => *x - width + padding,
+                    _ => *x - width / 2.0,
+                };
+                Some(Shape::Rect {
+                    x: left,
+                    y: *y - font_size * 0.8 - padding,
+                    width,
+                    height: font_size + 2.0 * padding,
+                })
+            }
+        }
+    }
+
+    fn bounds(&self, margin: f32) -> (f32, f32, f32, f32) {
+        let (min_x, min_y, max_x, max_y) = match self {
+            Shape::Circle { x, y, radius } => (x - radius, y - radius, x + radius, y + radius),
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => (*x, *y, x + width, y + height),
+            Shape::Path { points, .. } => points.iter().fold(
+                (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+                |(min_x, min_y, max_x, max_y), &(x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            ),
+        };
+        (
+            min_x - margin,
+            min_y - margin,
+            max_x + margin,
+            max_y + margin,
+        )
+    }
+
+    fn contains(&self, px: f32, py: f32) -> bool {
+        match self {
+            Shape::Circle { x, y, radius } => {
+                (px - x).powi(2) + (py - y).powi(2) <= radius * radius
+            }
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => px >= *x && px <= x + width && py >= *y && py <= y + height,
+            Shape::Path { points, filled } => *filled && winding_number(points, px, py) != 0,
+        }
+    }
+
+    fn on_stroke(&self, px: f32, py: f32, width: f32, dash: Option<(f32, f32)>) -> bool {
+        let half_width = width / 2.0;
+        match self {
+            Shape::Circle { x, y, radius } => {
+                let distance = ((px - x).powi(2) + (py - y).powi(2)).sqrt();
+                (distance - radius).abs() <= half_width
+            }
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let inside_outer = px >= x - half_width
+                    && px <= x + width + half_width
+                    && py >= y - half_width
+                    && py <= y + height + half_width;
+                let inside_inner = px > x + half_width
+                    && px < x + width - half_width
+                    && py > y + half_width
+                    && py < y + height - half_width;
+                inside_outer && !inside_inner
+            }
+            Shape::Path { points, .. } => {
+                let mut length_before = 0.0;
+                for segment in points.windows(2) {
+                    let (x1, y1) = segment[0];
+                    let (x2, y2) = segment[1];
+                    let (dx, dy) = (x2 - x1, y2 - y1);
+                    let length = (dx * dx + dy * dy).sqrt();
+                    let t = if length > 0.0 {
+                        (((px - x1) * dx + (py - y1) * dy) / (length * length)).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    let distance = ((px - x1 - t * dx).powi(2) + (py - y1 - t * dy).powi(2)).sqrt();
+                    let dashed_out = match dash {
+                        Some((on, off)) => (length_before + t * length) % (on + off) > on,
+                        None => false,
+                    };
+                    if distance <= half_width && !dashed_out {
+                        return true;
+                    }
+                    length_before += length;
+                }
+                false
+            }
+        }
+    }
+}
+
+fn winding_number(points: &[(f32, f32)], px: f32, py: f32) -> i32 {
+    let mut winding = 0;
+    for i in 0..points.len() {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % points.len()];
+        let side = (x2 - x1) * (py - y1) - (px - x1) * (y2 - y1);
+        if y1 <= py {
+            if y2 > py && side > 0.0 {
+                winding += 1;
+            }
+        } else if y2 <= py && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Parses colors in `#RGB`, `#RRGGBB` and `#RRGGBBAA` formats as well as a few named colors.
+fn parse_color(color: &str) -> Option<[f32; 4]> {
+    let hex = match color.to_ascii_lowercase().as_str() {
+        "transparent" => return None,
+        "white" => "ffffff".to_string(),
+        "black" => "000000".to_string(),
+        "red" => "ff0000".to_string(),
+        "green" => "008000".to_string(),
+        "blue" => "0000ff".to_string(),
+        "yellow" => "ffff00".to_string(),
+        "gray" | "grey" => "808080".to_string(),
+        color => color.strip_prefix('#')?.to_string(),
+    };
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect::<String>() + "ff",
+        6 => hex + "ff",
+        8 => hex,
+        _ => return None,
+    };
+    let mut channels = [0.0; 4];
+    for (i, channel) in channels.iter_mut().enumerate() {
+        *channel = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()? as f32 / 255.0;
+    }
+    Some(channels)
+}
+
+fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Compresses the data into a zlib stream using a single fixed Huffman deflate block. Only runs
+/// of repeated pixels are found, which is enough for the large uniform areas of room images.
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    const PIXEL_SIZE: usize = 4;
+    const MAX_MATCH: usize = 258;
+
+    let mut writer = BitWriter::default();
+    // Final block with fixed Huffman codes.
+    writer.write_bits(1, 1);
+    writer.write_bits(1, 2);
+
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        if i >= PIXEL_SIZE {
+            while length < MAX_MATCH
+                && i + length < data.len()
+                && data[i + length] == data[i + length - PIXEL_SIZE]
+            {
+                length += 1;
+            }
+        }
+        if length >= 3 {
+            writer.write_length(length);
+            // Distance code 3 is distance 4 with no extra bits.
+            writer.write_huffman(3, 5);
+            i += length;
+        } else {
+            writer.write_literal(data[i]);
+            i += 1;
+        }
+    }
+    writer.write_literal_or_length_code(256);
+
+    let mut zlib = vec![0x78, 0x01];
+    zlib.extend(writer.finish());
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bit_count: u32,
+}
+
+impl BitWriter {
+    /// Writes the bits starting from the least significant one.
+    fn write_bits(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.bit_count;
+        self.bit_count += count;
+        while self.bit_count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code starting from the most significant bit.
+    fn write_huffman(&mut self, code: u32, length: u32) {
+        let reversed = code.reverse_bits() >> (32 - length);
+        self.write_bits(reversed, length);
+    }
+
+    fn write_literal(&mut self, literal: u8) {
+        self.write_literal_or_length_code(literal as u32);
+    }
+
+    fn write_literal_or_length_code(&mut self, code: u32) {
+        match code {
+            0..=143 => self.write_huffman(0x30 + code, 8),
+            144..=255 => self.write_huffman(0x190 + code - 144, 9),
+            256..=279 => self.write_huffman(code - 256, 7),
+            _ => self.write_huffman(0xC0 + code - 280, 8),
+        }
+    }
+
+    fn write_length(&mut self, length: usize) {
+        const BASES: [usize; 29] = [
+            3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99,
+            115, 131, 163, 195, 227, 258,
+        ];
+        const EXTRA_BITS: [u32; 29] = [
+            0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+        ];
+        let index = BASES.iter().rposition(|&base| base <= length).unwrap();
+        self.write_literal_or_length_code(257 + index as u32);
+        self.write_bits((length - BASES[index]) as u32, EXTRA_BITS[index]);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::json::{to_json_value, JsonValue};
+use crate::RoomVisualBackend;
+use screeps::{CircleStyle, LineStyle, PolyStyle, RectStyle, TextStyle};
+use serde::Serialize;
+use std::fmt;
+
+/// Backend that records the drawn primitives as plain data instead of drawing them.
+///
+/// Works outside of the game, e.g., in native unit tests, and may be used as a source for other
+/// renderers.
+#[derive(Clone, Debug, Default)]
+pub struct RecordingBackend {
+    pub visuals: Vec<RecordedVisual>,
+}
+
+impl RecordingBackend {
+    pub fn new() -> Self {
+        RecordingBackend::default()
+    }
+
+    /// Returns the recorded primitives and clears the recording.
+    pub fn take(&mut self) -> Vec<RecordedVisual> {
+        std::mem::take(&mut self.visuals)
+    }
+}
+
+impl RoomVisualBackend for RecordingBackend {
+    fn circle(&mut self, x: f32, y: f32, style: Option<CircleStyle>) {
+        self.visuals.push(RecordedVisual::Circle {
+            x,
+            y,
+            style: RecordedStyle::from_style(style),
+        });
+    }
+
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), style: Option<LineStyle>) {
+        self.visuals.push(RecordedVisual::Line {
+            from,
+            to,
+            style: RecordedStyle::from_style(style),
+        });
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, style: Option<RectStyle>) {
+        self.visuals.push(RecordedVisual::Rect {
+            x,
+            y,
+            width,
+            height,
+            style: RecordedStyle::from_style(style),
+        });
+    }
+
+    fn poly(&mut self, points: Vec<(f32, f32)>, style: Option<PolyStyle>) {
+        self.visuals.push(RecordedVisual::Poly {
+            points,
+            style: RecordedStyle::from_style(style),
+        });
+    }
+
+    fn text(&mut self, x: f32, y: f32, text: String, style: Option<TextStyle>) {
+        self.visuals.push(RecordedVisual::Text {
+            x,
+            y,
+            text,
+            style: RecordedStyle::from_style(style),
+        });
+    }
+}
+
+/// A single primitive drawn into the `RecordingBackend`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordedVisual {
+    Circle {
+        x: f32,
+        y: f32,
+        style: RecordedStyle,
+    },
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        style: RecordedStyle,
+    },
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        style: RecordedStyle,
+    },
+    Poly {
+        points: Vec<(f32, f32)>,
+        style: RecordedStyle,
+    },
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        style: RecordedStyle,
+    },
+}
+
+impl RecordedVisual {
+    pub fn style(&self) -> &RecordedStyle {
+        match self {
+            RecordedVisual::Circle { style, .. } => style,
+            RecordedVisual::Line { style, .. } => style,
+            RecordedVisual::Rect { style, .. } => style,
+            RecordedVisual::Poly { style, .. } => style,
+            RecordedVisual::Text { style, .. } => style,
+        }
+    }
+
+    /// Fill color with the game's default applied. `None` if the primitive is not filled.
+    pub fn fill(&self) -> Option<&str> {
+        let style = self.style();
+        let fill = match self {
+            RecordedVisual::Circle { .. } | RecordedVisual::Rect { .. } => {
+                Some(style.fill.as_deref().unwrap_or(DEFAULT_COLOR))
+            }
+            RecordedVisual::Poly { .. } => style.fill.as_deref(),
+            RecordedVisual::Text { .. } => Some(style.color.as_deref().unwrap_or(DEFAULT_COLOR)),
+            RecordedVisual::Line { .. } => None,
+        };
+        fill.filter(|&color| color != TRANSPARENT)
+    }
+
+    /// Stroke color and width with the game's defaults applied. `None` if the primitive has no
+    /// stroke. The stroke of a line is the line itself.
+    pub fn stroke(&self) -> Option<(&str, f32)> {
+        let style = self.style();
+        let stroke = match self {
+            RecordedVisual::Line { .. } => Some((
+                style.color.as_deref().unwrap_or(DEFAULT_COLOR),
+                style.width.unwrap_or(DEFAULT_STROKE_WIDTH),
+            )),
+            RecordedVisual::Circle { .. } | RecordedVisual::Rect { .. } => style
+                .stroke
+                .as_deref()
+                .map(|color| (color, style.stroke_width.unwrap_or(DEFAULT_STROKE_WIDTH))),
+            RecordedVisual::Poly { .. } => Some((
+                style.stroke.as_deref().unwrap_or(DEFAULT_COLOR),
+                style.stroke_width.unwrap_or(DEFAULT_STROKE_WIDTH),
+            )),
+            RecordedVisual::Text { .. } => style.stroke.as_deref().map(|color| {
+                (
+                    color,
+                    style.stroke_width.unwrap_or(DEFAULT_TEXT_STROKE_WIDTH),
+                )
+            }),
+        };
+        stroke.filter(|&(color, width)| color != TRANSPARENT && width > 0.0)
+    }
+
+    /// Opacity with the game's default applied.
+    pub fn opacity(&self) -> f32 {
+        let default_opacity = match self {
+            RecordedVisual::Text { .. } => 1.0,
+            _ => DEFAULT_OPACITY,
+        };
+        self.style().opacity.unwrap_or(default_opacity)
+    }
+
+    /// Radius of a circle with the game's default applied.
+    pub fn radius(&self) -> f32 {
+        self.style().radius.unwrap_or(DEFAULT_RADIUS)
+    }
+
+    /// Font size of a text in tiles with the game's default applied.
+    pub fn font_size(&self) -> f32 {
+        match &self.style().font {
+            Some(RecordedFont::Size(size)) => *size,
+            Some(RecordedFont::Custom(font)) => font
+                .split_whitespace()
+                .find_map(|part| part.parse().ok())
+                .unwrap_or(DEFAULT_FONT_SIZE),
+            None => DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+// Defaults of the in-game RoomVisual.
+const DEFAULT_COLOR: &str = "#FFFFFF";
+const TRANSPARENT: &str = "transparent";
+const DEFAULT_STROKE_WIDTH: f32 = 0.1;
+const DEFAULT_TEXT_STROKE_WIDTH: f32 = 0.15;
+const DEFAULT_OPACITY: f32 = 0.5;
+const DEFAULT_RADIUS: f32 = 0.15;
+const DEFAULT_FONT_SIZE: f32 = 0.5;
+
+/// Style of a recorded primitive. Properties not set when drawing are `None`, meaning that the
+/// game's defaults apply. Only the properties applicable to the primitive's kind may be set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedStyle {
+    pub radius: Option<f32>,
+    pub width: Option<f32>,
+    pub color: Option<String>,
+    pub fill: Option<String>,
+    pub stroke: Option<String>,
+    pub stroke_width: Option<f32>,
+    pub opacity: Option<f32>,
+    pub line_style: Option<String>,
+    pub font: Option<RecordedFont>,
+    pub background_color: Option<String>,
+    pub background_padding: Option<f32>,
+    pub align: Option<String>,
+}
+
+impl RecordedStyle {
+    /// Reads the properties of any of the `screeps` styles. The styles do not expose their fields,
+    /// so they are read from their serialized form, which is also what the game receives.
+    pub fn from_style<S: Serialize>(style: Option<S>) -> Self {
+        style
+            .map(|style| {
+                let value = to_json_value(&style).expect("expect serialize style");
+                let number = |key| value.get(key).and_then(JsonValue::as_f32);
+                let string = |key| value.get(key).and_then(JsonValue::as_str).map(String::from);
+                RecordedStyle {
+                    radius: number("radius"),
+                    width: number("width"),
+                    color: string("color"),
+                    fill: string("fill"),
+                    stroke: string("stroke"),
+                    stroke_width: number("strokeWidth"),
+                    opacity: number("opacity"),
+                    line_style: string("lineStyle"),
+                    font: value.get("font").and_then(|font| match font {
+                        JsonValue::Number(size) => Some(RecordedFont::Size(*size as f32)),
+                        JsonValue::String(font) => Some(RecordedFont::Custom(font.clone())),
+                        _ => None,
+                    }),
+                    background_color: string("backgroundColor"),
+                    background_padding: number("backgroundPadding"),
+                    align: string("align"),
+                }
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordedFont {
+    Size(f32),
+    Custom(String),
+}
+
+impl fmt::Display for RecordedVisual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordedVisual::Circle { x, y, style } => write!(f, "circle ({x}, {y}){style}"),
+            RecordedVisual::Line { from, to, style } => write!(
+                f,
+                "line ({}, {}) -> ({}, {}){style}",
+                from.0, from.1, to.0, to.1
+            ),
+            RecordedVisual::Rect {
+                x,
+                y,
+                width,
+                height,
+                style,
+            } => write!(f, "rect ({x}, {y}) {width}x{height}{style}"),
+            RecordedVisual::Poly { points, style } => {
+                write!(f, "poly [")?;
+                for (i, (x, y)) in points.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "({x}, {y})")?;
+                }
+                write!(f, "]{style}")
+            }
+            RecordedVisual::Text { x, y, text, style } => {
+                write!(f, "text ({x}, {y}) {text:?}{style}")
+            }
+        }
+    }
+}
+
+/// Writes the set properties as ` name=value` pairs.
+impl fmt::Display for RecordedStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_property<T: fmt::Display>(
+            f: &mut fmt::Formatter<'_>,
+            name: &str,
+            value: &Option<T>,
+        ) -> fmt::Result {
+            match value {
+                Some(value) => write!(f, " {name}={value}"),
+                None => Ok(()),
+            }
+        }
+
+        write_property(f, "radius", &self.radius)?;
+        write_property(f, "width", &self.width)?;
+        write_property(f, "color", &self.color)?;
+        write_property(f, "fill", &self.fill)?;
+        write_property(f, "stroke", &self.stroke)?;
+        write_property(f, "stroke_width", &self.stroke_width)?;
+        write_property(f, "opacity", &self.opacity)?;
+        write_property(f, "line_style", &self.line_style)?;
+        match &self.font {
+            Some(RecordedFont::Size(size)) => write!(f, " font={size}")?,
+            Some(RecordedFont::Custom(font)) => write!(f, " font={font:?}")?,
+            None => {}
+        }
+        write_property(f, "background_color", &self.background_color)?;
+        write_property(f, "background_padding", &self.background_padding)?;
+        write_property(f, "align", &self.align)
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::collections::HashMap;
+
+/// Number of tiles along each side of a room.
+pub(crate) const ROOM_SIZE: usize = 50;
+
+/// A rectangle of tiles: the top-left tile and the numbers of tiles along each side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TileRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Covers the tiles of a room for which the predicate holds with few disjoint rectangles.
+///
+/// Each row is split into horizontal runs of the tiles, and runs spanning the same columns in
+/// consecutive rows are merged into a single rectangle.
+pub(crate) fn merged_rects<F: Fn(usize, usize) -> bool>(contains: F) -> Vec<TileRect> {
+    let mut rects = Vec::new();
+    // Rectangles that end in the previous row, which may still be extended.
+    let mut open_rects: Vec<TileRect> = Vec::new();
+    for y in 0..ROOM_SIZE {
+        let mut next_open_rects = Vec::new();
+        let mut x = 0;
+        while x < ROOM_SIZE {
+            if !contains(x, y) {
+                x += 1;
+                continue;
+            }
+            let start_x = x;
+            while x < ROOM_SIZE && contains(x, y) {
+                x += 1;
+            }
+            let width = x - start_x;
+            let rect = match open_rects
+                .iter()
+                .position(|rect| rect.x == start_x && rect.width == width)
+            {
+                Some(i) => {
+                    let mut rect = open_rects.swap_remove(i);
+                    rect.height += 1;
+                    rect
+                }
+                None => TileRect {
+                    x: start_x,
+                    y,
+                    width,
+                    height: 1,
+                },
+            };
+            next_open_rects.push(rect);
+        }
+        rects.append(&mut open_rects);
+        open_rects = next_open_rects;
+    }
+    rects.append(&mut open_rects);
+    rects.sort_by_key(|rect| (rect.y, rect.x));
+    rects
+}
+
+/// Traces the boundaries of the regions of tiles of a room for which the predicate holds.
+///
+/// Returns closed loops of tile corners, where the corner `(x, y)` is the top-left corner of the
+/// tile `(x, y)`, with the first corner repeated at the end and without corners in the middle of
+/// straight sides. Outer boundaries go clockwise and boundaries of holes counterclockwise. Regions
+/// touching only diagonally get separate loops.
+pub(crate) fn boundary_loops<F: Fn(usize, usize) -> bool>(contains: F) -> Vec<Vec<(usize, usize)>> {
+    let contains_i = |x: isize, y: isize| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < ROOM_SIZE
+            && (y as usize) < ROOM_SIZE
+            && contains(x as usize, y as usize)
+    };
+
+    // Sides of the tiles between the region and the outside, directed so that the region is on the
+    // right, stored by their starting corners.
+    let mut edges: HashMap<(isize, isize), Vec<(isize, isize)>> = HashMap::new();
+    let mut starts = Vec::new();
+    for y in 0..ROOM_SIZE as isize {
+        for x in 0..ROOM_SIZE as isize {
+            if !contains_i(x, y) {
+                continue;
+            }
+            for (start, direction, outside) in [
+                ((x, y), (1, 0), (x, y - 1)),
+                ((x + 1, y), (0, 1), (x + 1, y)),
+                ((x + 1, y + 1), (-1, 0), (x, y + 1)),
+                ((x, y + 1), (0, -1), (x - 1, y)),
+            ] {
+                if !contains_i(outside.0, outside.1) {
+                    edges.entry(start).or_default().push(direction);
+                    starts.push(start);
+                }
+            }
+        }
+    }
+
+    let mut loops = Vec::new();
+    for start in starts {
+        let Some(&first_direction) = edges.get(&start).and_then(|directions| directions.first())
+        else {
+            continue;
+        };
+        let mut corners = vec![start];
+        let (mut corner, mut direction) = (start, first_direction);
+        loop {
+            let directions = edges.get_mut(&corner).expect("expect a boundary edge");
+            directions.retain(|&other| other != direction);
+            if directions.is_empty() {
+                edges.remove(&corner);
+            }
+            corner = (corner.0 + direction.0, corner.1 + direction.1);
+
+            // Turning right first keeps regions touching only diagonally apart.
+            let next_direction = [
+                (-direction.1, direction.0),
+                direction,
+                (direction.1, -direction.0),
+            ]
+            .into_iter()
+            .find(|&next| {
+                (corner == start && next == first_direction)
+                    || edges
+                        .get(&corner)
+                        .is_some_and(|directions| directions.contains(&next))
+            })
+            .expect("expect the boundary to continue");
+            if corner == start && next_direction == first_direction {
+                break;
+            }
+            if next_direction != direction {
+                corners.push(corner);
+            }
+            direction = next_direction;
+        }
+        // The start lies in the middle of a side if the loop does not turn there.
+        if direction == first_direction {
+            corners.remove(0);
+        }
+        corners.push(corners[0]);
+        loops.push(
+            corners
+                .into_iter()
+                .map(|(x, y)| (x as usize, y as usize))
+                .collect(),
+        );
+    }
+    loops
+}
@@ -0,0 +1,297 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::json::{to_json_value, JsonValue};
+use crate::label::compact_amount;
+use crate::{DetailLevel, RoomVisualBackend, RoomVisualExt, Theme};
+use screeps::{CircleStyle, RectStyle, ResourceType, TextStyle};
+use std::rc::Rc;
+
+/// Radius of a resource badge.
+const BADGE_SIZE: f32 = 0.25;
+const BADGE_FONT_SIZE: f32 = 0.3;
+
+/// Shape of a resource badge.
+enum Badge {
+    /// A plain circle, used by energy and power.
+    Fluid,
+    /// A circle with a letter, used by minerals and deposits.
+    Mineral,
+    /// A filled box with the compound's formula.
+    Compound,
+    /// An outlined box with an abbreviation, used by commodities.
+    Commodity,
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws the icon of given resource, colored and lettered like in the game, e.g., to annotate
+    /// stores or dropped resources. If given, the amount is written below it.
+    pub fn resource(
+        &mut self,
+        x: f32,
+        y: f32,
+        resource_type: ResourceType,
+        amount: Option<u32>,
+        opacity: f32,
+    ) {
+        let minimal = self.effective_detail_level() == DetailLevel::Minimal;
+        let theme = Rc::clone(&self.theme);
+        let (color, dark_color) = resource_colors(&theme, resource_type);
+        let label = resource_label(resource_type);
+
+        match resource_badge(resource_type) {
+            Badge::Fluid => {
+                let fluid_circle_style = CircleStyle::default()
+                    .radius(BADGE_SIZE)
+                    .fill(color)
+                    .opacity(opacity);
+                self.circle(x, y, Some(fluid_circle_style));
+            }
+            Badge::Mineral => {
+                let outer_circle_style = CircleStyle::default()
+                    .radius(BADGE_SIZE)
+                    .fill(color)
+                    .opacity(opacity);
+                self.circle(x, y, Some(outer_circle_style));
+                if minimal {
+                    return;
+                }
+
+                let inner_circle_style = CircleStyle::default()
+                    .radius(BADGE_SIZE * 0.8)
+                    .fill(dark_color)
+                    .opacity(opacity);
+                self.circle(x, y, Some(inner_circle_style));
+                self.badge_text(x, y, label, color, opacity);
+            }
+            badge => {
+                let compound = matches!(badge, Badge::Compound);
+                let width = label.chars().count() as f32 * BADGE_FONT_SIZE * 0.6 + 0.1;
+                let height = BADGE_FONT_SIZE + 0.1;
+                let box_rect_style = if compound {
+                    RectStyle::default().fill(color)
+                } else {
+                    RectStyle::default()
+                        .fill(dark_color)
+                        .stroke(color)
+                        .stroke_width(0.04)
+                }
+                .opacity(opacity);
+                self.rect(
+                    x - width / 2.0,
+                    y - height / 2.0,
+                    width,
+                    height,
+                    Some(box_rect_style),
+                );
+                if minimal {
+                    return;
+                }
+
+                let text_color = if compound { dark_color } else { color };
+                self.badge_text(x, y, label, text_color, opacity);
+            }
+        }
+
+        if let Some(amount) = amount.filter(|_| !minimal) {
+            self.label(x, y + 0.45, compact_amount(amount), opacity);
+        }
+    }
+
+    fn badge_text(&mut self, x: f32, y: f32, text: String, color: &str, opacity: f32) {
+        let badge_text_style = TextStyle::default()
+            .color(color)
+            .font(BADGE_FONT_SIZE)
+            .opacity(opacity);
+        self.text(x, y + 0.35 * BADGE_FONT_SIZE, text, Some(badge_text_style));
+    }
+}
+
+/// Name of the resource as used by the game, e.g., `H` or `silicon`. `ResourceType` exposes its name
+/// only through its `Serialize` implementation.
+pub(crate) fn resource_name(resource_type: ResourceType) -> String {
+    match to_json_value(&resource_type) {
+        Ok(JsonValue::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// Whether the resource is a mineral compound, named by its formula. Single-letter names of base
+/// minerals are rejected, but they are only drawn as minerals because `resource_badge` lists them.
+fn is_compound(name: &str) -> bool {
+    name.len() > 1
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+fn resource_badge(resource_type: ResourceType) -> Badge {
+    match resource_type {
+        ResourceType::Energy | ResourceType::Power => Badge::Fluid,
+        ResourceType::Hydrogen
+        | ResourceType::Oxygen
+        | ResourceType::Utrium
+        | ResourceType::Lemergium
+        | ResourceType::Keanium
+        | ResourceType::Zynthium
+        | ResourceType::Catalyst
+        | ResourceType::Ghodium
+        | ResourceType::Silicon
+        | ResourceType::Metal
+        | ResourceType::Biomass
+        | ResourceType::Mist => Badge::Mineral,
+        ResourceType::Ops => Badge::Compound,
+        // Base minerals must stay listed above, since anything not a compound is a commodity.
+        _ if is_compound(&resource_name(resource_type)) => Badge::Compound,
+        _ => Badge::Commodity,
+    }
+}
+
+/// Text on the resource's badge: the symbol of a mineral, the formula of a compound with subscript
+/// digits or a short abbreviation of other resources.
+pub(crate) fn resource_label(resource_type: ResourceType) -> String {
+    let abbreviation = match resource_type {
+        ResourceType::Energy | ResourceType::Power => "",
+        ResourceType::Silicon => "Si",
+        ResourceType::Metal => "Me",
+        ResourceType::Biomass => "Bi",
+        ResourceType::Mist => "Mi",
+        ResourceType::Ops => "ops",
+        ResourceType::UtriumBar => "U",
+        ResourceType::LemergiumBar => "L",
+        ResourceType::ZynthiumBar => "Z",
+        ResourceType::KeaniumBar => "K",
+        ResourceType::GhodiumMelt => "G",
+        ResourceType::Oxidant => "O",
+        ResourceType::Reductant => "H",
+        ResourceType::Purifier => "X",
+        ResourceType::Battery => "E",
+        ResourceType::Composite => "Cm",
+        ResourceType::Crystal => "Cr",
+        ResourceType::Liquid => "Lq",
+        ResourceType::Wire => "Wi",
+        ResourceType::Switch => "Sw",
+        ResourceType::Transistor => "Tr",
+        ResourceType::Microchip => "Mc",
+        ResourceType::Circuit => "Ci",
+        ResourceType::Device => "De",
+        ResourceType::Cell => "Ce",
+        ResourceType::Phlegm => "Ph",
+        ResourceType::Tissue => "Ti",
+        ResourceType::Muscle => "Mu",
+        ResourceType::Organoid => "Od",
+        ResourceType::Organism => "Om",
+        ResourceType::Alloy => "Al",
+        ResourceType::Tube => "Tu",
+        ResourceType::Fixtures => "Fx",
+        ResourceType::Frame => "Fr",
+        ResourceType::Hydraulics => "Hy",
+        ResourceType::Machine => "Ma",
+        ResourceType::Condensate => "Cd",
+        ResourceType::Concentrate => "Cc",
+        ResourceType::Extract => "Ex",
+        ResourceType::Spirit => "Sp",
+        ResourceType::Emanation => "Em",
+        ResourceType::Essence => "Es",
+        _ => {
+            let name = resource_name(resource_type);
+            return if is_compound(&name) || name.len() == 1 {
+                name.replace('2', "₂")
+            } else {
+                name.chars().take(2).collect()
+            };
+        }
+    };
+    abbreviation.to_string()
+}
+
+/// Bright and dark colors of the resource's badge. Compounds and bars take the colors of their
+/// main mineral and commodities the colors of their deposit.
+pub(crate) fn resource_colors(theme: &Theme, resource_type: ResourceType) -> (&str, &str) {
+    match resource_type {
+        ResourceType::Energy | ResourceType::Battery => {
+            (theme.energy.as_str(), theme.resource_yellow.1.as_str())
+        }
+        ResourceType::Power | ResourceType::Ops => {
+            (theme.power.as_str(), theme.resource_red.1.as_str())
+        }
+        ResourceType::Hydrogen
+        | ResourceType::Oxygen
+        | ResourceType::Oxidant
+        | ResourceType::Reductant => color_pair(&theme.resource_grey),
+        ResourceType::Utrium | ResourceType::UtriumBar => color_pair(&theme.resource_blue),
+        ResourceType::Lemergium | ResourceType::LemergiumBar => color_pair(&theme.resource_green),
+        ResourceType::Keanium | ResourceType::KeaniumBar => color_pair(&theme.resource_purple),
+        ResourceType::Zynthium | ResourceType::ZynthiumBar => color_pair(&theme.resource_yellow),
+        ResourceType::Catalyst | ResourceType::Purifier => color_pair(&theme.resource_red),
+        ResourceType::Ghodium
+        | ResourceType::GhodiumMelt
+        | ResourceType::Composite
+        | ResourceType::Crystal
+        | ResourceType::Liquid => color_pair(&theme.resource_white),
+        ResourceType::Silicon
+        | ResourceType::Wire
+        | ResourceType::Switch
+        | ResourceType::Transistor
+        | ResourceType::Microchip
+        | ResourceType::Circuit
+        | ResourceType::Device => color_pair(&theme.resource_silicon),
+        ResourceType::Metal
+        | ResourceType::Alloy
+        | ResourceType::Tube
+        | ResourceType::Fixtures
+        | ResourceType::Frame
+        | ResourceType::Hydraulics
+        | ResourceType::Machine => color_pair(&theme.resource_metal),
+        ResourceType::Biomass
+        | ResourceType::Cell
+        | ResourceType::Phlegm
+        | ResourceType::Tissue
+        | ResourceType::Muscle
+        | ResourceType::Organoid
+        | ResourceType::Organism => color_pair(&theme.resource_biomass),
+        ResourceType::Mist
+        | ResourceType::Condensate
+        | ResourceType::Concentrate
+        | ResourceType::Extract
+        | ResourceType::Spirit
+        | ResourceType::Emanation
+        | ResourceType::Essence => color_pair(&theme.resource_purple),
+        _ => {
+            let name = resource_name(resource_type);
+            // Catalyzed compounds take the colors of the mineral after the catalyst.
+            let main_mineral = if name.len() > 2 {
+                name.trim_start_matches('X').chars().next()
+            } else {
+                name.chars().next()
+            };
+            match main_mineral {
+                Some('U') => color_pair(&theme.resource_blue),
+                Some('L') => color_pair(&theme.resource_green),
+                Some('K') => color_pair(&theme.resource_purple),
+                Some('Z') => color_pair(&theme.resource_yellow),
+                Some('G') => color_pair(&theme.resource_white),
+                // Hydroxide and other compounds of oxygen or hydrogen.
+                _ => color_pair(&theme.resource_grey),
+            }
+        }
+    }
+}
+
+fn color_pair((bright, dark): &(String, String)) -> (&str, &str) {
+    (bright, dark)
+}
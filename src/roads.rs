@@ -0,0 +1,123 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{room_xy, DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{LineStyle, RoomXY};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Roads known to a `RoomVisualExt`, connected to each other as soon as both are known.
+#[derive(Default)]
+pub(crate) struct RoadNetwork {
+    /// Drawn roads with their opacities.
+    drawn: HashMap<RoomXY, f32>,
+    /// Roads existing in the game, which are connected to but not drawn.
+    existing: HashSet<RoomXY>,
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Marks roads existing in the room, e.g., from `room.find(find::STRUCTURES)`, so that drawn
+    /// roads connect to them. The existing roads themselves are not drawn, but their connections to
+    /// already drawn roads are.
+    pub fn add_existing_roads<I: IntoIterator<Item = RoomXY>>(&mut self, roads: I) {
+        let mut connections = Vec::new();
+        for xy in roads {
+            // Drawn roads are already connected to all known neighbors.
+            if self.roads.existing.insert(xy) && !self.roads.drawn.contains_key(&xy) {
+                for near_xy in neighbors(xy) {
+                    if let Some(&near_opacity) = self.roads.drawn.get(&near_xy) {
+                        connections.push((xy, near_xy, near_opacity));
+                    }
+                }
+            }
+        }
+        self.draw_road_connections(connections);
+    }
+
+    /// Registers a road drawn in the (x, y) tile and connects it to the neighboring roads, drawn or
+    /// existing. Each connection has the lower of the two roads' opacities. Roads outside of the
+    /// room are drawn, but not connected. A road drawn more than once keeps its highest opacity for
+    /// the connections to roads drawn after it, while its connections drawn before are kept as they
+    /// are.
+    pub(crate) fn register_road(&mut self, x: f32, y: f32, opacity: f32) {
+        // Road construction sites are not connected.
+        if self.site_outline.is_some() {
+            return;
+        }
+        let xy = match room_xy(x, y) {
+            Ok(xy) => xy,
+            Err(_) => return,
+        };
+        if let Some(road_opacity) = self.roads.drawn.get_mut(&xy) {
+            *road_opacity = road_opacity.max(opacity);
+            return;
+        }
+
+        // An existing road is already connected to the drawn ones.
+        let was_existing = self.roads.existing.contains(&xy);
+        let mut connections = Vec::new();
+        for near_xy in neighbors(xy) {
+            let near_opacity = if let Some(&near_opacity) = self.roads.drawn.get(&near_xy) {
+                if was_existing {
+                    continue;
+                }
+                near_opacity
+            } else if self.roads.existing.contains(&near_xy) {
+                1.0
+            } else {
+                continue;
+            };
+            connections.push((xy, near_xy, opacity.min(near_opacity)));
+        }
+        self.roads.drawn.insert(xy, opacity);
+        self.draw_road_connections(connections);
+    }
+
+    fn draw_road_connections(&mut self, connections: Vec<(RoomXY, RoomXY, f32)>) {
+        let theme = Rc::clone(&self.theme);
+        self.in_layer(DrawLayer::ROADS, |ext| {
+            for (xy, near_xy, opacity) in connections {
+                // Lines go from the top left, the same regardless of which road was drawn first.
+                let (xy, near_xy) = if (xy.y, xy.x) <= (near_xy.y, near_xy.x) {
+                    (xy, near_xy)
+                } else {
+                    (near_xy, xy)
+                };
+                let road_line_style = LineStyle::default()
+                    .width(0.3)
+                    .color(&theme.road)
+                    .opacity(opacity);
+                ext.line(
+                    (xy.x.u8() as f32, xy.y.u8() as f32),
+                    (near_xy.x.u8() as f32, near_xy.y.u8() as f32),
+                    Some(road_line_style),
+                );
+            }
+        });
+    }
+}
+
+/// Tiles around given tile within the room.
+fn neighbors(xy: RoomXY) -> impl Iterator<Item = RoomXY> {
+    let (x, y) = (xy.x.u8() as i16, xy.y.u8() as i16);
+    (y - 1..=y + 1)
+        .flat_map(move |near_y| (x - 1..=x + 1).map(move |near_x| (near_x, near_y)))
+        .filter(move |&near| near != (x, y))
+        .filter_map(|(near_x, near_y)| {
+            RoomXY::try_from((u8::try_from(near_x).ok()?, u8::try_from(near_y).ok()?)).ok()
+        })
+}
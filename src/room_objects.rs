@@ -0,0 +1,116 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::resource::{resource_colors, resource_label};
+use crate::{DetailLevel, DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{CircleStyle, PolyStyle, RectStyle, ResourceType, TextStyle};
+use std::rc::Rc;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws an energy source.
+    pub fn source(&mut self, x: f32, y: f32, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| ext.draw_source(x, y, opacity));
+    }
+
+    fn draw_source(&mut self, x: f32, y: f32, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let half_size = match self.effective_detail_level() {
+            DetailLevel::Minimal => 0.3,
+            _ => 0.4,
+        };
+        let source_rect_style = RectStyle::default()
+            .fill(&theme.energy)
+            .stroke(&theme.source_outline)
+            .stroke_width(0.1)
+            .opacity(opacity);
+        self.rect(
+            x - half_size,
+            y - half_size,
+            2.0 * half_size,
+            2.0 * half_size,
+            Some(source_rect_style),
+        );
+    }
+
+    /// Draws a mineral deposit of given type, colored and lettered like in the game. Other types are
+    /// drawn with the colors and text of their resource badge.
+    pub fn mineral(&mut self, x: f32, y: f32, mineral_type: ResourceType, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_mineral(x, y, mineral_type, opacity)
+        });
+    }
+
+    fn draw_mineral(&mut self, x: f32, y: f32, mineral_type: ResourceType, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let (color, dark_color) = resource_colors(&theme, mineral_type);
+        let mineral_circle_style = CircleStyle::default()
+            .radius(0.45)
+            .fill(dark_color)
+            .stroke(color)
+            .stroke_width(0.08)
+            .opacity(opacity);
+        self.circle(x, y, Some(mineral_circle_style));
+
+        if self.effective_detail_level() != DetailLevel::Minimal {
+            let letter_style = TextStyle::default().color(color).font(0.5).opacity(opacity);
+            self.text(
+                x,
+                y + 0.175,
+                resource_label(mineral_type),
+                Some(letter_style),
+            );
+        }
+    }
+
+    /// Draws a deposit of given type, colored and labeled with the first two letters of its type.
+    /// Other types are drawn with the colors and text of their resource badge.
+    pub fn deposit(&mut self, x: f32, y: f32, deposit_type: ResourceType, opacity: f32) {
+        self.in_layer(DrawLayer::STRUCTURES, |ext| {
+            ext.draw_deposit(x, y, deposit_type, opacity)
+        });
+    }
+
+    fn draw_deposit(&mut self, x: f32, y: f32, deposit_type: ResourceType, opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        let (color, dark_color) = resource_colors(&theme, deposit_type);
+        let deposit_poly_points = vec![
+            (-0.1, -0.5),
+            (0.3, -0.4),
+            (0.5, -0.05),
+            (0.35, 0.4),
+            (-0.05, 0.5),
+            (-0.45, 0.3),
+            (-0.5, -0.15),
+            (-0.1, -0.5),
+        ]
+        .into_iter()
+        .map(|(poly_x, poly_y)| (x + poly_x, y + poly_y))
+        .collect();
+        let deposit_poly_style = PolyStyle::default()
+            .fill(dark_color)
+            .stroke(color)
+            .stroke_width(0.08)
+            .opacity(opacity);
+        self.poly(deposit_poly_points, Some(deposit_poly_style));
+
+        if self.effective_detail_level() != DetailLevel::Minimal {
+            let abbreviation = resource_label(deposit_type);
+            let abbreviation_style = TextStyle::default().color(color).font(0.4).opacity(opacity);
+            self.text(x, y + 0.14, abbreviation, Some(abbreviation_style));
+        }
+    }
+}
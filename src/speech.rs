@@ -0,0 +1,186 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::controller::arc_points;
+use crate::{RoomVisualBackend, RoomVisualExt};
+use screeps::{PolyStyle, TextStyle};
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::rc::Rc;
+
+const PADDING: f32 = 0.15;
+const CORNER_RADIUS: f32 = 0.15;
+const TAIL_HALF_WIDTH: f32 = 0.15;
+/// Distance of the tip of the tail from the center of the tile.
+const TAIL_TIP_DISTANCE: f32 = 0.3;
+/// Distance of the bubble from the center of the tile.
+const BUBBLE_DISTANCE: f32 = 0.6;
+/// Room edges, including the halves of the border tiles.
+const ROOM_MIN: f32 = -0.5;
+const ROOM_MAX: f32 = 49.5;
+
+/// Style of a speech bubble, a builder like the `screeps` styles.
+#[derive(Clone, Debug)]
+pub struct SpeechStyle {
+    background: Option<String>,
+    color: Option<String>,
+    font_size: f32,
+    opacity: f32,
+    flip_at_room_edge: bool,
+}
+
+impl Default for SpeechStyle {
+    fn default() -> Self {
+        SpeechStyle {
+            background: None,
+            color: None,
+            font_size: 0.5,
+            opacity: 1.0,
+            flip_at_room_edge: false,
+        }
+    }
+}
+
+impl SpeechStyle {
+    /// Color of the bubble. Defaults to `Theme::speech_background`.
+    pub fn background(mut self, val: &str) -> SpeechStyle {
+        self.background = Some(val.to_string());
+        self
+    }
+
+    /// Color of the text. Defaults to `Theme::speech_text`.
+    pub fn color(mut self, val: &str) -> SpeechStyle {
+        self.color = Some(val.to_string());
+        self
+    }
+
+    /// Font size in tiles. Defaults to 0.5.
+    pub fn font_size(mut self, val: f32) -> SpeechStyle {
+        self.font_size = val;
+        self
+    }
+
+    pub fn opacity(mut self, val: f32) -> SpeechStyle {
+        self.opacity = val;
+        self
+    }
+
+    /// Whether to draw the bubble below the tile instead of above it when it would go past the top
+    /// of the room, and shift it horizontally to stay within the room. Defaults to false.
+    pub fn flip_at_room_edge(mut self, val: bool) -> SpeechStyle {
+        self.flip_at_room_edge = val;
+        self
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws a speech bubble over the (x, y) tile with its tail pointing at the tile, like the
+    /// `speech` of screepers' RoomVisual. The text may have multiple lines separated by `\n`. The
+    /// bubble's size is estimated from the number and length of the lines.
+    pub fn speech(&mut self, x: f32, y: f32, text: &str, style: Option<SpeechStyle>) {
+        let theme = Rc::clone(&self.theme);
+        let style = style.unwrap_or_default();
+        let lines = text.lines().collect::<Vec<_>>();
+        let line_height = style.font_size * 1.2;
+        let max_line_length = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = max_line_length as f32 * style.font_size * 0.6 + 2.0 * PADDING;
+        let height = lines.len().max(1) as f32 * line_height + 2.0 * PADDING;
+
+        let mut left = x - width / 2.0;
+        let mut top = y - BUBBLE_DISTANCE - height;
+        let mut flipped = false;
+        if style.flip_at_room_edge {
+            left = left.min(ROOM_MAX - width).max(ROOM_MIN);
+            if top < ROOM_MIN {
+                top = y + BUBBLE_DISTANCE;
+                flipped = true;
+            }
+        }
+        let right = left + width;
+        let bottom = top + height;
+        let corner_radius = CORNER_RADIUS.min(width / 2.0).min(height / 2.0);
+        // The tail stays within the straight part of the bubble's edge.
+        let tail_x = x
+            .max(left + corner_radius + TAIL_HALF_WIDTH)
+            .min(right - corner_radius - TAIL_HALF_WIDTH);
+
+        let corner = |corner_x: f32, corner_y: f32, start_angle: f32| {
+            arc_points(
+                corner_x,
+                corner_y,
+                corner_radius,
+                start_angle,
+                start_angle + FRAC_PI_2,
+                3,
+            )
+        };
+        let mut bubble_points = corner(left + corner_radius, top + corner_radius, PI);
+        if flipped {
+            bubble_points.extend([
+                (tail_x - TAIL_HALF_WIDTH, top),
+                (x, y + TAIL_TIP_DISTANCE),
+                (tail_x + TAIL_HALF_WIDTH, top),
+            ]);
+        }
+        bubble_points.extend(corner(
+            right - corner_radius,
+            top + corner_radius,
+            -FRAC_PI_2,
+        ));
+        bubble_points.extend(corner(right - corner_radius, bottom - corner_radius, 0.0));
+        if !flipped {
+            bubble_points.extend([
+                (tail_x + TAIL_HALF_WIDTH, bottom),
+                (x, y - TAIL_TIP_DISTANCE),
+                (tail_x - TAIL_HALF_WIDTH, bottom),
+            ]);
+        }
+        bubble_points.extend(corner(
+            left + corner_radius,
+            bottom - corner_radius,
+            FRAC_PI_2,
+        ));
+        bubble_points.push(bubble_points[0]);
+        let bubble_poly_style = PolyStyle::default()
+            .fill(
+                style
+                    .background
+                    .as_deref()
+                    .unwrap_or(&theme.speech_background),
+            )
+            .stroke("transparent")
+            .opacity(style.opacity);
+        self.poly(bubble_points, Some(bubble_poly_style));
+
+        let center_x = (left + right) / 2.0;
+        for (i, line) in lines.into_iter().enumerate() {
+            let line_style = TextStyle::default()
+                .color(style.color.as_deref().unwrap_or(&theme.speech_text))
+                .font(style.font_size)
+                .opacity(style.opacity);
+            self.text(
+                center_x,
+                top + PADDING + i as f32 * line_height + line_height / 2.0 + 0.35 * style.font_size,
+                line.to_string(),
+                Some(line_style),
+            );
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::RecordedVisual;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+const ROOM_SIZE: f32 = 50.0;
+const PLAIN_COLOR: &str = "#2B2B2B";
+
+/// Renders recorded primitives into an SVG document of a whole room, 50x50 tiles.
+///
+/// The tile (x, y) is centered at the point (x, y), just like in the game. Style properties that
+/// were not set use the same defaults as the game.
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    pixels_per_tile: f32,
+    background: Option<String>,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            pixels_per_tile: 16.0,
+            background: Some(PLAIN_COLOR.to_string()),
+        }
+    }
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        SvgRenderer::default()
+    }
+
+    /// Size of a single tile in the output image. Defaults to 16.
+    pub fn pixels_per_tile(mut self, val: f32) -> Self {
+        self.pixels_per_tile = val;
+        self
+    }
+
+    /// Color of the room's background. Defaults to the color of plains in the game.
+    pub fn background(mut self, val: &str) -> Self {
+        self.background = Some(val.to_string());
+        self
+    }
+
+    /// Leaves the room's background transparent.
+    pub fn no_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+
+    /// Returns the SVG document with given primitives drawn in order.
+    pub fn render(&self, visuals: &[RecordedVisual]) -> String {
+        let size = ROOM_SIZE * self.pixels_per_tile;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="-0.5 -0.5 {ROOM_SIZE} {ROOM_SIZE}">"#
+        )
+        .unwrap();
+        if let Some(background) = &self.background {
+            writeln!(
+                svg,
+                r#"<rect x="-0.5" y="-0.5" width="{ROOM_SIZE}" height="{ROOM_SIZE}" fill="{background}"/>"#
+            )
+            .unwrap();
+        }
+        for visual in visuals {
+            write_element(&mut svg, visual);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the SVG document with given primitives drawn in order to a file.
+    pub fn write<P: AsRef<Path>>(&self, visuals: &[RecordedVisual], path: P) -> io::Result<()> {
+        std::fs::write(path, self.render(visuals))
+    }
+}
+
+fn write_element(svg: &mut String, visual: &RecordedVisual) {
+    let paint = paint_attributes(visual);
+    match visual {
+        RecordedVisual::Circle { x, y, .. } => {
+            let radius = visual.radius();
+            writeln!(svg, r#"<circle cx="{x}" cy="{y}" r="{radius}"{paint}/>"#).unwrap();
+        }
+        RecordedVisual::Line { from, to, .. } => {
+            writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{paint}/>"#,
+                from.0, from.1, to.0, to.1
+            )
+            .unwrap();
+        }
+        RecordedVisual::Rect {
+            x,
+            y,
+            width,
+            height,
+            ..
+        } => {
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{height}"{paint}/>"#
+            )
+            .unwrap();
+        }
+        RecordedVisual::Poly { points, .. } => {
+            let points = points
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(svg, r#"<polyline points="{points}"{paint}/>"#).unwrap();
+        }
+        RecordedVisual::Text { x, y, text, style } => {
+            let font_size = visual.font_size();
+            if let Some(background) = &style.background_color {
+                // SVG has no text backgrounds, so the text's size is approximated.
+                let padding = style.background_padding.unwrap_or(0.3);
+                let width = text.chars().count() as f32 * font_size * 0.6 + 2.0 * padding;
+                let height = font_size + 2.0 * padding;
+                let left = match style.align.as_deref() {
+                    Some("left") => *x - padding,
+                    Some("right") => *x - width + padding,
+                    _ => *x - width / 2.0,
+                };
+                let top = *y - font_size * 0.8 - padding;
+                writeln!(
+                    svg,
+                    r#"<rect x="{left}" y="{top}" width="{width}" height="{height}" fill="{background}" opacity="{}"/>"#,
+                    visual.opacity()
+                )
+                .unwrap();
+            }
+            let anchor = match style.align.as_deref() {
+                Some("left") => "start",
+                Some("right") => "end",
+                _ => "middle",
+            };
+            writeln!(
+                svg,
+                r#"<text x="{x}" y="{y}" font-size="{font_size}" font-family="Arial, sans-serif" text-anchor="{anchor}"{paint}>{}</text>"#,
+                escape(text)
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn paint_attributes(visual: &RecordedVisual) -> String {
+    let mut attributes = String::new();
+    match visual.fill() {
+        Some(fill) => write!(attributes, r#" fill="{fill}""#).unwrap(),
+        None => attributes.push_str(r#" fill="none""#),
+    }
+    if let Some((stroke, stroke_width)) = visual.stroke() {
+        write!(
+            attributes,
+            r#" stroke="{stroke}" stroke-width="{stroke_width}""#
+        )
+        .unwrap();
+        match visual.style().line_style.as_deref() {
+            Some("dashed") => write!(
+                attributes,
+                r#" stroke-dasharray="{} {}""#,
+                stroke_width * 4.0,
+                stroke_width * 2.0
+            )
+            .unwrap(),
+            Some("dotted") => write!(
+                attributes,
+                r#" stroke-dasharray="{} {}""#,
+                stroke_width,
+                stroke_width * 2.0
+            )
+            .unwrap(),
+            _ => {}
+        }
+    }
+    write!(attributes, r#" opacity="{}""#, visual.opacity()).unwrap();
+    attributes
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
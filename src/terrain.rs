@@ -0,0 +1,53 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::regions::{merged_rects, ROOM_SIZE};
+use crate::{DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{RectStyle, Terrain};
+use std::rc::Rc;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws the walls and swamps of a room in the colors of the game. Plains are not drawn, so the
+    /// background shows through them.
+    ///
+    /// The terrain is indexed by `[y][x]`, e.g., filled from `RoomTerrain::get(x, y)`. Tiles of the
+    /// same terrain are merged into rectangles to use little of the visual data limit. Draw the
+    /// terrain before other visuals, so that it does not cover them, or use the deferred mode, in
+    /// which the terrain goes into its own bottom layer.
+    pub fn terrain(&mut self, terrain: &[[Terrain; ROOM_SIZE]; ROOM_SIZE], opacity: f32) {
+        self.in_layer(DrawLayer::TERRAIN, |ext| ext.draw_terrain(terrain, opacity));
+    }
+
+    fn draw_terrain(&mut self, terrain: &[[Terrain; ROOM_SIZE]; ROOM_SIZE], opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        for (terrain_type, color) in [
+            (Terrain::Swamp, &theme.swamp),
+            (Terrain::Wall, &theme.terrain_wall),
+        ] {
+            for rect in merged_rects(|x, y| terrain[y][x] == terrain_type) {
+                let terrain_rect_style = RectStyle::default().fill(color).opacity(opacity);
+                self.rect(
+                    rect.x as f32 - 0.5,
+                    rect.y as f32 - 0.5,
+                    rect.width as f32,
+                    rect.height as f32,
+                    Some(terrain_rect_style),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::{RoomVisualBackend, RoomVisualExt};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Colors of all glyphs, e.g., `Theme::game()` or `Theme::colorblind()`.
+///
+/// The colors are in any format accepted by the game, e.g., `#RRGGBB`. Custom themes may be created
+/// by modifying one of the presets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Background of most structures.
+    pub dark: String,
+    /// Light parts of structures, e.g., the spawn's ring, the tower's barrel, the terminal's inside
+    /// and the level segments of the controller.
+    pub light: String,
+    /// Outline of most structures, used for own structures.
+    pub outline: String,
+    /// Outline of structures of allies.
+    pub ally_outline: String,
+    /// Outline of structures of enemies.
+    pub hostile_outline: String,
+    /// Outline of unowned structures and structures of other players.
+    pub neutral_outline: String,
+    /// Outlines of structures of players with given usernames.
+    pub player_outlines: HashMap<String, String>,
+    /// Middle parts of structures, e.g., the lab's interior, the container and the factory, the
+    /// controller's level ring and the powers of power creeps on cooldown.
+    pub gray: String,
+    /// Energy in structures and energy badges.
+    pub energy: String,
+    /// Power in structures and power badges.
+    pub power: String,
+    pub road: String,
+    pub white: String,
+    pub black: String,
+    /// Fill of ramparts, drawn at 30% of the opacity.
+    pub rampart: String,
+    pub rampart_outline: String,
+    pub factory_outer_circle_fill: String,
+    pub factory_parts: String,
+    pub factory_level_circle_fill: String,
+    pub keeper: String,
+    pub portal: String,
+    pub portal_fill: String,
+    pub invader: String,
+    pub source_outline: String,
+    /// Outline of the structures of construction sites.
+    pub construction_site: String,
+    pub move_part: String,
+    pub work_part: String,
+    pub carry_part: String,
+    pub attack_part: String,
+    pub ranged_attack_part: String,
+    pub heal_part: String,
+    pub claim_part: String,
+    pub tough_part: String,
+    pub swamp: String,
+    pub terrain_wall: String,
+    /// Color of full health bars, blended with `health_medium` towards half of the hits.
+    pub health_high: String,
+    /// Color of health bars at half of the hits.
+    pub health_medium: String,
+    /// Color of empty health bars, blended with `health_medium` towards half of the hits.
+    pub health_low: String,
+    /// Background of speech bubbles, unless set in their `SpeechStyle`.
+    pub speech_background: String,
+    /// Text of speech bubbles, unless set in their `SpeechStyle`.
+    pub speech_text: String,
+    /// Animated position markers, unless set in their `AnimatedPositionStyle`.
+    pub position_marker: String,
+    /// Bright and dark colors of resource badges of ghodium and white commodities.
+    pub resource_white: (String, String),
+    /// Bright and dark colors of resource badges of hydrogen, oxygen and their compounds.
+    pub resource_grey: (String, String),
+    /// Bright and dark colors of resource badges of catalyst, purifier and power.
+    pub resource_red: (String, String),
+    /// Bright and dark colors of resource badges of zynthium and energy.
+    pub resource_yellow: (String, String),
+    /// Bright and dark colors of resource badges of lemergium.
+    pub resource_green: (String, String),
+    /// Bright and dark colors of resource badges of utrium.
+    pub resource_blue: (String, String),
+    /// Bright and dark colors of resource badges of keanium and mist.
+    pub resource_purple: (String, String),
+    pub resource_silicon: (String, String),
+    pub resource_metal: (String, String),
+    pub resource_biomass: (String, String),
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::game()
+    }
+}
+
+impl Theme {
+    /// Colors as close to the game as possible. The default.
+    pub fn game() -> Self {
+        Theme {
+            dark: "#181818".to_string(),
+            light: "#CCCCCC".to_string(),
+            outline: "#8FBB93".to_string(),
+            ally_outline: "#6BA6E0".to_string(),
+            hostile_outline: "#E05C5C".to_string(),
+            neutral_outline: "#A8A8A8".to_string(),
+            player_outlines: HashMap::new(),
+            gray: "#555555".to_string(),
+            energy: "#FFE87B".to_string(),
+            power: "#F53547".to_string(),
+            road: "#666666".to_string(),
+            white: "#FFFFFF".to_string(),
+            black: "#000000".to_string(),
+            rampart: "#669966".to_string(),
+            rampart_outline: "#66CC66".to_string(),
+            factory_outer_circle_fill: "#232323".to_string(),
+            factory_parts: "#140a0a".to_string(),
+            factory_level_circle_fill: "#302a2a".to_string(),
+            keeper: "#8C2F2F".to_string(),
+            portal: "#75C8F4".to_string(),
+            portal_fill: "#24394F".to_string(),
+            invader: "#E05A2B".to_string(),
+            source_outline: "#A89447".to_string(),
+            construction_site: "#B4C8B6".to_string(),
+            move_part: "#A9B7C6".to_string(),
+            work_part: "#FFE56D".to_string(),
+            carry_part: "#777777".to_string(),
+            attack_part: "#F93842".to_string(),
+            ranged_attack_part: "#5D80B2".to_string(),
+            heal_part: "#65FD62".to_string(),
+            claim_part: "#B99CFB".to_string(),
+            tough_part: "#858585".to_string(),
+            swamp: "#232C0F".to_string(),
+            terrain_wall: "#111111".to_string(),
+            health_high: "#4CD44C".to_string(),
+            health_medium: "#FFE87B".to_string(),
+            health_low: "#F53547".to_string(),
+            speech_background: "#2CCF3B".to_string(),
+            speech_text: "#000000".to_string(),
+            position_marker: "#0000FF".to_string(),
+            resource_white: pair("#FFFFFF", "#4C4C4C"),
+            resource_grey: pair("#B4B4B4", "#4C4C4C"),
+            resource_red: pair("#FF7B7B", "#592121"),
+            resource_yellow: pair("#FDD388", "#5D4C2E"),
+            resource_green: pair("#00F4A2", "#236144"),
+            resource_blue: pair("#50D7F9", "#006181"),
+            resource_purple: pair("#A071FF", "#371383"),
+            resource_silicon: pair("#4DA7E5", "#1D3F57"),
+            resource_metal: pair("#C49A82", "#4A3A31"),
+            resource_biomass: pair("#84B012", "#324307"),
+        }
+    }
+
+    /// Black backgrounds, white details and fully saturated colors, e.g., for small zoom levels.
+    pub fn high_contrast() -> Self {
+        Theme {
+            dark: "#000000".to_string(),
+            light: "#FFFFFF".to_string(),
+            outline: "#00FF80".to_string(),
+            ally_outline: "#40A0FF".to_string(),
+            hostile_outline: "#FF2020".to_string(),
+            neutral_outline: "#E0E0E0".to_string(),
+            player_outlines: HashMap::new(),
+            gray: "#808080".to_string(),
+            energy: "#FFFF00".to_string(),
+            power: "#FF0040".to_string(),
+            road: "#A0A0A0".to_string(),
+            white: "#FFFFFF".to_string(),
+            black: "#000000".to_string(),
+            rampart: "#00C040".to_string(),
+            rampart_outline: "#00FF60".to_string(),
+            factory_outer_circle_fill: "#202020".to_string(),
+            factory_parts: "#000000".to_string(),
+            factory_level_circle_fill: "#404040".to_string(),
+            keeper: "#FF4000".to_string(),
+            portal: "#00E0FF".to_string(),
+            portal_fill: "#003060".to_string(),
+            invader: "#FF8000".to_string(),
+            source_outline: "#FFC000".to_string(),
+            construction_site: "#FFFFFF".to_string(),
+            move_part: "#E0F0FF".to_string(),
+            work_part: "#FFFF00".to_string(),
+            carry_part: "#909090".to_string(),
+            attack_part: "#FF0000".to_string(),
+            ranged_attack_part: "#4080FF".to_string(),
+            heal_part: "#00FF00".to_string(),
+            claim_part: "#C080FF".to_string(),
+            tough_part: "#C0C0C0".to_string(),
+            swamp: "#304000".to_string(),
+            terrain_wall: "#000000".to_string(),
+            health_high: "#00FF00".to_string(),
+            health_medium: "#FFFF00".to_string(),
+            health_low: "#FF0000".to_string(),
+            speech_background: "#00FF00".to_string(),
+            speech_text: "#000000".to_string(),
+            position_marker: "#4080FF".to_string(),
+            resource_white: pair("#FFFFFF", "#404040"),
+            resource_grey: pair("#C0C0C0", "#404040"),
+            resource_red: pair("#FF4040", "#600000"),
+            resource_yellow: pair("#FFE000", "#604800"),
+            resource_green: pair("#00FF80", "#006030"),
+            resource_blue: pair("#00C0FF", "#004070"),
+            resource_purple: pair("#A060FF", "#300080"),
+            resource_silicon: pair("#40A0FF", "#003060"),
+            resource_metal: pair("#E0A070", "#503020"),
+            resource_biomass: pair("#A0E000", "#304000"),
+        }
+    }
+
+    /// Colors from the Okabe-Ito palette, which stay distinguishable with all common kinds of color
+    /// blindness. Colors that differ in the game only by hue also differ in lightness.
+    pub fn colorblind() -> Self {
+        Theme {
+            outline: "#56B4E9".to_string(),
+            ally_outline: "#CC79A7".to_string(),
+            hostile_outline: "#D55E00".to_string(),
+            neutral_outline: "#999999".to_string(),
+            energy: "#F0E442".to_string(),
+            power: "#D55E00".to_string(),
+            rampart: "#009E73".to_string(),
+            rampart_outline: "#5FD4B3".to_string(),
+            keeper: "#CC79A7".to_string(),
+            portal: "#56B4E9".to_string(),
+            portal_fill: "#0B3A57".to_string(),
+            invader: "#E69F00".to_string(),
+            source_outline: "#E69F00".to_string(),
+            move_part: "#FFFFFF".to_string(),
+            work_part: "#F0E442".to_string(),
+            carry_part: "#777777".to_string(),
+            attack_part: "#D55E00".to_string(),
+            ranged_attack_part: "#0072B2".to_string(),
+            heal_part: "#009E73".to_string(),
+            claim_part: "#CC79A7".to_string(),
+            tough_part: "#AAAAAA".to_string(),
+            health_high: "#0072B2".to_string(),
+            health_medium: "#F0E442".to_string(),
+            health_low: "#D55E00".to_string(),
+            speech_background: "#009E73".to_string(),
+            speech_text: "#000000".to_string(),
+            position_marker: "#0072B2".to_string(),
+            resource_red: pair("#D55E00", "#4D2200"),
+            resource_yellow: pair("#F0E442", "#58531A"),
+            resource_green: pair("#009E73", "#00392A"),
+            resource_blue: pair("#56B4E9", "#0B3A57"),
+            resource_purple: pair("#CC79A7", "#4A2C3D"),
+            resource_silicon: pair("#0072B2", "#002A42"),
+            resource_metal: pair("#E69F00", "#533A00"),
+            resource_biomass: pair("#B8D94A", "#3D4A10"),
+            ..Theme::game()
+        }
+    }
+
+    /// Game colors converted to shades of gray, e.g., for plan previews where the layout matters
+    /// more than the structures' details.
+    pub fn monochrome() -> Self {
+        Theme::game().map_colors(grayscale)
+    }
+
+    fn map_colors(self, f: fn(&str) -> String) -> Self {
+        let map_pair = |(bright, dark): (String, String)| (f(&bright), f(&dark));
+        Theme {
+            dark: f(&self.dark),
+            light: f(&self.light),
+            outline: f(&self.outline),
+            ally_outline: f(&self.ally_outline),
+            hostile_outline: f(&self.hostile_outline),
+            neutral_outline: f(&self.neutral_outline),
+            player_outlines: self
+                .player_outlines
+                .into_iter()
+                .map(|(username, color)| (username, f(&color)))
+                .collect(),
+            gray: f(&self.gray),
+            energy: f(&self.energy),
+            power: f(&self.power),
+            road: f(&self.road),
+            white: f(&self.white),
+            black: f(&self.black),
+            rampart: f(&self.rampart),
+            rampart_outline: f(&self.rampart_outline),
+            factory_outer_circle_fill: f(&self.factory_outer_circle_fill),
+            factory_parts: f(&self.factory_parts),
+            factory_level_circle_fill: f(&self.factory_level_circle_fill),
+            keeper: f(&self.keeper),
+            portal: f(&self.portal),
+            portal_fill: f(&self.portal_fill),
+            invader: f(&self.invader),
+            source_outline: f(&self.source_outline),
+            construction_site: f(&self.construction_site),
+            move_part: f(&self.move_part),
+            work_part: f(&self.work_part),
+            carry_part: f(&self.carry_part),
+            attack_part: f(&self.attack_part),
+            ranged_attack_part: f(&self.ranged_attack_part),
+            heal_part: f(&self.heal_part),
+            claim_part: f(&self.claim_part),
+            tough_part: f(&self.tough_part),
+            swamp: f(&self.swamp),
+            terrain_wall: f(&self.terrain_wall),
+            health_high: f(&self.health_high),
+            health_medium: f(&self.health_medium),
+            health_low: f(&self.health_low),
+            speech_background: f(&self.speech_background),
+            speech_text: f(&self.speech_text),
+            position_marker: f(&self.position_marker),
+            resource_white: map_pair(self.resource_white),
+            resource_grey: map_pair(self.resource_grey),
+            resource_red: map_pair(self.resource_red),
+            resource_yellow: map_pair(self.resource_yellow),
+            resource_green: map_pair(self.resource_green),
+            resource_blue: map_pair(self.resource_blue),
+            resource_purple: map_pair(self.resource_purple),
+            resource_silicon: map_pair(self.resource_silicon),
+            resource_metal: map_pair(self.resource_metal),
+            resource_biomass: map_pair(self.resource_biomass),
+        }
+    }
+}
+
+fn pair(bright: &str, dark: &str) -> (String, String) {
+    (bright.to_string(), dark.to_string())
+}
+
+/// Gray of the same luma as given `#RRGGBB` color. Other colors are returned unchanged.
+fn grayscale(color: &str) -> String {
+    match rgb(color) {
+        Some((r, g, b)) => {
+            let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+            let luma = luma.round() as u8;
+            format!("#{luma:02X}{luma:02X}{luma:02X}")
+        }
+        None => color.to_string(),
+    }
+}
+
+/// Color at given fraction between two `#RRGGBB` colors. If either color has another format,
+/// the nearer one is returned.
+pub(crate) fn blend(from: &str, to: &str, fraction: f32) -> String {
+    match (rgb(from), rgb(to)) {
+        (Some(from), Some(to)) => {
+            let channel = |from: u8, to: u8| {
+                (from as f32 + (to as f32 - from as f32) * fraction).round() as u8
+            };
+            format!(
+                "#{:02X}{:02X}{:02X}",
+                channel(from.0, to.0),
+                channel(from.1, to.1),
+                channel(from.2, to.2)
+            )
+        }
+        _ if fraction < 0.5 => from.to_string(),
+        _ => to.to_string(),
+    }
+}
+
+/// Channels of a `#RRGGBB` color.
+fn rgb(color: &str) -> Option<(u8, u8, u8)> {
+    let channel = |i: usize| {
+        color
+            .get(i..i + 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+    };
+    if color.len() == 7 && color.starts_with('#') {
+        Some((channel(1)?, channel(3)?, channel(5)?))
+    } else {
+        None
+    }
+}
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Colors of the glyphs, `Theme::game()` by default.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets the colors of subsequently drawn glyphs.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Rc::new(theme);
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2023 Xilexio
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+// associated documentation files (the “Software”), to deal in the Software without restriction,
+// including without limitation the rights to use, copy, modify, merge, publish, distribute,
+// sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+// NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use crate::regions::{boundary_loops, merged_rects, ROOM_SIZE};
+use crate::{DrawLayer, RoomVisualBackend, RoomVisualExt};
+use screeps::{LineDrawStyle, LineStyle, PolyStyle, RectStyle, RoomXY};
+use std::rc::Rc;
+
+impl<B: RoomVisualBackend> RoomVisualExt<B> {
+    /// Draws constructed walls on given tiles as merged regions. Each region is filled without inner
+    /// borders and outlined only along its perimeter, and the stripes of each horizontal run of walls
+    /// are drawn as a pair of dashed lines.
+    ///
+    /// Uses a few primitives per region and row instead of three per wall. In the deferred mode, the
+    /// walls go into the structures layer.
+    pub fn walls<I: IntoIterator<Item = RoomXY>>(&mut self, walls: I, opacity: f32) {
+        let mut tiles = [[false; ROOM_SIZE]; ROOM_SIZE];
+        for xy in walls {
+            tiles[xy.y.u8() as usize][xy.x.u8() as usize] = true;
+        }
+        self.in_layer(DrawLayer::STRUCTURES, |ext| ext.draw_walls(&tiles, opacity));
+    }
+
+    fn draw_walls(&mut self, tiles: &[[bool; ROOM_SIZE]; ROOM_SIZE], opacity: f32) {
+        let theme = Rc::clone(&self.theme);
+        for rect in merged_rects(|x, y| tiles[y][x]) {
+            let wall_rect_style = RectStyle::default().fill(&theme.dark).opacity(opacity);
+            self.rect(
+                rect.x as f32 - 0.5,
+                rect.y as f32 - 0.5,
+                rect.width as f32,
+                rect.height as f32,
+                Some(wall_rect_style),
+            );
+        }
+        for corners in boundary_loops(|x, y| tiles[y][x]) {
+            let wall_outline_style = PolyStyle::default()
+                .stroke(&theme.black)
+                .stroke_width(0.07)
+                .opacity(opacity);
+            let points = corners
+                .into_iter()
+                .map(|(x, y)| (x as f32 - 0.5, y as f32 - 0.5))
+                .collect();
+            self.poly(points, Some(wall_outline_style));
+        }
+        for (y, row) in tiles.iter().enumerate() {
+            let mut x = 0;
+            while x < ROOM_SIZE {
+                if !row[x] {
+                    x += 1;
+                    continue;
+                }
+                let start_x = x;
+                while x < ROOM_SIZE && row[x] {
+                    x += 1;
+                }
+                let (left, right, y) = (start_x as f32, (x - 1) as f32, y as f32);
+                // Staggered like the stripes of a single wall.
+                for (from, to) in [
+                    ((left - 0.25, y - 0.15), (right + 0.05, y - 0.15)),
+                    ((left - 0.05, y + 0.15), (right + 0.25, y + 0.15)),
+                ] {
+                    let wall_stripe_line_style = LineStyle::default()
+                        .width(0.05)
+                        .color(&theme.road)
+                        .line_style(LineDrawStyle::Dashed)
+                        .opacity(opacity);
+                    self.line(from, to, Some(wall_stripe_line_style));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{AnimatedPositionStyle, RecordedVisual};
+
+fn marker(tick: u32, style: Option<AnimatedPositionStyle>) -> Vec<RecordedVisual> {
+    let mut ext = recording_ext();
+    ext.animated_position(25.0, 25.0, tick, style);
+    ext.room_visual.take()
+}
+
+#[test]
+fn animated_position_frames() {
+    let visuals = (0..3).flat_map(|tick| marker(tick, None)).collect::<Vec<_>>();
+    assert_snapshot("animated_position_frames", &visuals);
+}
+
+#[test]
+fn animation_depends_only_on_tick() {
+    assert_eq!(marker(1234, None), marker(1234, None));
+    assert_ne!(marker(1234, None), marker(1235, None));
+    // The animation repeats every `frames` ticks.
+    let style = AnimatedPositionStyle::default().frames(4);
+    assert_eq!(marker(7, Some(style.clone())), marker(11, Some(style)));
+}
+
+#[test]
+fn styled_marker() {
+    let style = AnimatedPositionStyle::default()
+        .color("#FF0000")
+        .radius(0.5)
+        .opacity(1.0);
+    assert_snapshot("animated_position_styled", &marker(3, Some(style)));
+}
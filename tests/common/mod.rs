@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+
+use room_visual_ext::{RecordedVisual, RecordingBackend, RoomVisualExt};
+use screeps::RoomXY;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn recording_ext() -> RoomVisualExt<RecordingBackend> {
+    RoomVisualExt::with_backend(RecordingBackend::new())
+}
+
+pub fn xy(x: u8, y: u8) -> RoomXY {
+    RoomXY::try_from((x, y)).unwrap()
+}
+
+pub fn lines(visuals: &[RecordedVisual]) -> Vec<((f32, f32), (f32, f32))> {
+    visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Line { from, to, .. } => Some((*from, *to)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Connection between two points with its opacity.
+pub type Connection = ((f32, f32), (f32, f32), f32);
+
+/// Lines with their top-left end first, sorted.
+pub fn connections(visuals: &[RecordedVisual]) -> Vec<Connection> {
+    let mut connections = visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Line { from, to, .. } => {
+                let (from, to) = if (from.1, from.0) <= (to.1, to.0) {
+                    (*from, *to)
+                } else {
+                    (*to, *from)
+                };
+                Some((from, to, visual.opacity()))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    connections.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    connections
+}
+
+pub fn texts(visuals: &[RecordedVisual]) -> Vec<&str> {
+    visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn strokes(visuals: &[RecordedVisual]) -> Vec<&str> {
+    visuals
+        .iter()
+        .filter_map(|visual| visual.stroke().map(|(color, _)| color))
+        .collect()
+}
+
+/// Fill and stroke colors of the visuals.
+pub fn colors(visuals: &[RecordedVisual]) -> Vec<&str> {
+    visuals
+        .iter()
+        .flat_map(|visual| {
+            visual
+                .fill()
+                .into_iter()
+                .chain(visual.stroke().map(|(color, _)| color))
+        })
+        .collect()
+}
+
+/// Compares the visuals with `tests/snapshots/<name>.txt`, one primitive per line.
+///
+/// Run the tests with `UPDATE_SNAPSHOTS=1` to write the current output as the new snapshots.
+pub fn assert_snapshot(name: &str, visuals: &[RecordedVisual]) {
+    let actual = visuals
+        .iter()
+        .map(|visual| format!("{visual}\n"))
+        .collect::<String>();
+    assert_file_snapshot(&format!("{name}.txt"), &actual);
+}
+
+/// Compares the text with `tests/snapshots/<file_name>`.
+pub fn assert_file_snapshot(file_name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(file_name);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        expected,
+        actual,
+        "snapshot {} differs, run with UPDATE_SNAPSHOTS=1 to update it",
+        path.display()
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{RecordedVisual, Theme};
+use screeps::StructureType;
+use std::collections::HashSet;
+
+#[test]
+fn construction_site_with_progress() {
+    let mut ext = recording_ext();
+    ext.construction_site(25.0, 25.0, StructureType::Extension, 1500, 3000, 1.0);
+    assert_snapshot("construction_site_extension", &ext.room_visual.visuals);
+}
+
+fn is_site_outline(visual: &RecordedVisual) -> bool {
+    visual.style().line_style.as_deref() == Some("dashed")
+}
+
+#[test]
+fn construction_site_is_an_outline_of_the_structure() {
+    let mut ext = recording_ext();
+    ext.construction_site(25.0, 25.0, StructureType::Tower, 0, 5000, 1.0);
+    let visuals = ext.room_visual.take();
+    ext.structure(25.0, 25.0, StructureType::Tower, 1.0);
+    let structure = ext.room_visual.take();
+
+    let outlines = visuals
+        .iter()
+        .filter(|visual| is_site_outline(visual))
+        .collect::<Vec<_>>();
+    assert_eq!(outlines.len(), structure.len());
+    for outline in outlines {
+        assert_eq!(outline.fill(), None);
+        assert_eq!(
+            outline.stroke().map(|(color, _)| color),
+            Some(Theme::game().construction_site.as_str())
+        );
+    }
+    // The ring, the outlines and the center, with no progress arc without progress.
+    assert_eq!(visuals.len(), structure.len() + 2);
+}
+
+#[test]
+fn small_structures_are_not_covered_by_the_ring() {
+    for structure_type in [StructureType::Road, StructureType::Link] {
+        let mut ext = recording_ext();
+        ext.construction_site(25.0, 25.0, structure_type, 0, 300, 1.0);
+        let visuals = &ext.room_visual.visuals;
+        let ring = visuals
+            .iter()
+            .position(|visual| {
+                matches!(visual, RecordedVisual::Circle { .. }) && visual.fill() == Some("#181818")
+            })
+            .unwrap();
+        let first_outline = visuals.iter().position(is_site_outline).unwrap();
+        assert!(ring < first_outline, "{structure_type:?}");
+    }
+}
+
+#[test]
+fn sites_of_different_structures_have_different_shapes() {
+    // Power spawns look like spawns in the game, except for the colors.
+    let structure_types = [
+        StructureType::Spawn,
+        StructureType::Extension,
+        StructureType::Road,
+        StructureType::Wall,
+        StructureType::Rampart,
+        StructureType::Link,
+        StructureType::Storage,
+        StructureType::Tower,
+        StructureType::Observer,
+        StructureType::Extractor,
+        StructureType::Lab,
+        StructureType::Terminal,
+        StructureType::Container,
+        StructureType::Nuker,
+        StructureType::Factory,
+    ];
+    let mut ext = recording_ext();
+    let shapes = structure_types
+        .iter()
+        .map(|&structure_type| {
+            ext.construction_site(25.0, 25.0, structure_type, 0, 300, 1.0);
+            ext.room_visual
+                .take()
+                .iter()
+                .filter(|visual| is_site_outline(visual))
+                .map(|visual| visual.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(shapes.len(), structure_types.len());
+}
+
+#[test]
+fn road_construction_sites_do_not_connect() {
+    let mut ext = recording_ext();
+    ext.structure(25.0, 25.0, StructureType::Road, 1.0);
+    ext.construction_site(26.0, 25.0, StructureType::Road, 100, 300, 1.0);
+    ext.structure(27.0, 25.0, StructureType::Road, 1.0);
+    assert!(!ext
+        .room_visual
+        .visuals
+        .iter()
+        .any(|visual| matches!(visual, RecordedVisual::Line { .. })));
+}
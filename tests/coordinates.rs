@@ -0,0 +1,60 @@
+mod common;
+
+use common::{lines, recording_ext, xy};
+use room_visual_ext::{room_xy, OutOfRoomError};
+use screeps::StructureType;
+
+#[test]
+fn coordinates_in_the_room_are_converted() {
+    assert_eq!(room_xy(0.0, 49.0).unwrap(), xy(0, 49));
+    assert_eq!(room_xy(10.4, 9.6).unwrap(), xy(10, 10));
+    assert_eq!(room_xy(-0.4, 49.4).unwrap(), xy(0, 49));
+}
+
+#[test]
+fn coordinates_outside_of_the_room_are_rejected() {
+    for (x, y) in [(-1.0, 10.0), (10.0, 50.0), (49.5, 10.0), (10.0, 300.0)] {
+        assert_eq!(room_xy(x, y), Err(OutOfRoomError { x, y }));
+    }
+    assert!(room_xy(f32::NAN, 10.0).is_err());
+}
+
+#[test]
+fn try_structure_draws_only_in_the_room() {
+    let mut ext = recording_ext();
+    assert!(ext
+        .try_structure(25.0, 25.0, StructureType::Spawn, 1.0)
+        .is_ok());
+    let drawn = ext.room_visual.visuals.len();
+    assert!(drawn > 0);
+
+    let error = ext
+        .try_structure(50.0, 25.0, StructureType::Spawn, 1.0)
+        .unwrap_err();
+    assert_eq!(error, OutOfRoomError { x: 50.0, y: 25.0 });
+    assert_eq!(
+        error.to_string(),
+        "coordinates (50, 25) are outside of the room"
+    );
+    assert_eq!(ext.room_visual.visuals.len(), drawn);
+}
+
+#[test]
+fn roads_on_room_edges_connect_only_within_the_room() {
+    let mut ext = recording_ext();
+    ext.structure(0.0, 0.0, StructureType::Road, 1.0);
+    ext.structure(1.0, 0.0, StructureType::Road, 1.0);
+    ext.structure(49.0, 49.0, StructureType::Road, 1.0);
+    assert_eq!(lines(&ext.room_visual.visuals), [((0.0, 0.0), (1.0, 0.0))]);
+}
+
+#[test]
+fn roads_outside_of_the_room_do_not_connect() {
+    let mut ext = recording_ext();
+    ext.structure(-1.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(0.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(50.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(49.0, 10.0, StructureType::Road, 1.0);
+    assert_eq!(ext.room_visual.visuals.len(), 4);
+    assert!(lines(&ext.room_visual.visuals).is_empty());
+}
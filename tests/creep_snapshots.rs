@@ -0,0 +1,82 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{DetailLevel, RecordedVisual};
+use screeps::{Part, PowerCreepClass};
+
+#[test]
+fn creep_with_mixed_body() {
+    let mut ext = recording_ext();
+    let body = [
+        Part::Tough,
+        Part::Tough,
+        Part::Work,
+        Part::Work,
+        Part::Carry,
+        Part::Attack,
+        Part::RangedAttack,
+        Part::Heal,
+        Part::Claim,
+        Part::Move,
+        Part::Move,
+        Part::Move,
+    ];
+    ext.creep(25.0, 25.0, &body, 1.0);
+    assert_snapshot("creep_mixed_body", &ext.room_visual.visuals);
+}
+
+#[test]
+fn full_move_ring_covers_the_circle() {
+    let mut ext = recording_ext();
+    ext.creep(25.0, 25.0, &[Part::Move; 50], 1.0);
+    let arcs = ext
+        .room_visual
+        .visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Poly { points, .. } => Some(points),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(arcs.len(), 1);
+    let (first, last) = (arcs[0][0], arcs[0][arcs[0].len() - 1]);
+    assert!((first.0 - last.0).abs() < 1e-4 && (first.1 - last.1).abs() < 1e-4);
+}
+
+#[test]
+fn minimal_creep_is_its_body() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Minimal);
+    ext.creep(25.0, 25.0, &[Part::Tough, Part::Work, Part::Move], 1.0);
+    assert_eq!(ext.room_visual.visuals.len(), 1);
+}
+
+#[test]
+fn power_creep_with_ready_powers() {
+    let mut ext = recording_ext();
+    ext.power_creep(
+        25.0,
+        25.0,
+        PowerCreepClass::Operator,
+        12,
+        Some(&[true, false, true]),
+        1.0,
+    );
+    assert_snapshot("power_creep_ready_powers", &ext.room_visual.visuals);
+}
+
+#[test]
+fn power_creep_at_half_opacity() {
+    let mut ext = recording_ext();
+    ext.power_creep(25.0, 25.0, PowerCreepClass::Operator, 3, None, 0.5);
+    assert!(ext
+        .room_visual
+        .visuals
+        .iter()
+        .all(|visual| visual.opacity() == 0.5));
+    assert!(!ext
+        .room_visual
+        .visuals
+        .iter()
+        .any(|visual| matches!(visual, RecordedVisual::Poly { .. })));
+}
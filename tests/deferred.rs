@@ -0,0 +1,107 @@
+mod common;
+
+use common::recording_ext;
+use room_visual_ext::{DrawLayer, RecordedVisual};
+use screeps::{CircleStyle, StructureType, Terrain};
+
+fn circle_radii(visuals: &[RecordedVisual]) -> Vec<f32> {
+    visuals
+        .iter()
+        .filter(|visual| matches!(visual, RecordedVisual::Circle { .. }))
+        .map(|visual| visual.radius())
+        .collect()
+}
+
+#[test]
+fn draws_wait_for_flush() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    ext.structure(25.0, 25.0, StructureType::Spawn, 1.0);
+    assert!(ext.room_visual.visuals.is_empty());
+    assert_eq!(ext.queued_len(), 2);
+
+    ext.flush();
+    assert_eq!(ext.queued_len(), 0);
+
+    let mut immediate = recording_ext();
+    immediate.structure(25.0, 25.0, StructureType::Spawn, 1.0);
+    assert_eq!(ext.room_visual.visuals, immediate.room_visual.visuals);
+}
+
+#[test]
+fn layers_are_flushed_by_priority() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+
+    ext.set_layer(DrawLayer::HUD);
+    ext.circle(10.0, 10.0, Some(CircleStyle::default().radius(0.5)));
+    ext.structure(10.0, 10.0, StructureType::Rampart, 1.0);
+    ext.set_layer(DrawLayer::new("paths", 150));
+    ext.circle(10.0, 10.0, Some(CircleStyle::default().radius(0.2)));
+    ext.structure(10.0, 10.0, StructureType::Extension, 1.0);
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    let mut terrain = [[Terrain::Plain; 50]; 50];
+    terrain[10][9] = Terrain::Swamp;
+    ext.terrain(&terrain, 1.0);
+    assert_eq!(ext.layer(), &DrawLayer::new("paths", 150));
+    ext.flush();
+
+    let visuals = &ext.room_visual.visuals;
+    // Terrain, road, path, extension, rampart and HUD.
+    assert!(matches!(visuals[0], RecordedVisual::Rect { .. }));
+    assert_eq!(circle_radii(visuals), [0.15, 0.2, 0.45, 0.32, 0.5]);
+    assert!(matches!(visuals[visuals.len() - 2], RecordedVisual::Rect { .. }));
+}
+
+#[test]
+fn draws_in_a_layer_keep_their_order() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    for radius in [0.3, 0.1, 0.2] {
+        ext.circle(25.0, 25.0, Some(CircleStyle::default().radius(radius)));
+    }
+    ext.flush();
+    assert_eq!(circle_radii(&ext.room_visual.visuals), [0.3, 0.1, 0.2]);
+}
+
+#[test]
+fn draws_are_immediate_after_leaving_deferred_mode() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    ext.circle(25.0, 25.0, None);
+    ext.set_deferred(false);
+    ext.circle(26.0, 25.0, None);
+    assert_eq!(ext.room_visual.visuals.len(), 1);
+    ext.flush();
+    assert_eq!(ext.room_visual.visuals.len(), 2);
+}
+
+#[test]
+fn into_backend_flushes_queued_draws() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    ext.circle(25.0, 25.0, None);
+    let backend = ext.into_backend();
+    assert_eq!(backend.visuals.len(), 1);
+}
+
+#[test]
+fn glyphs_go_into_their_layers() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    ext.set_layer(DrawLayer::HUD);
+    ext.circle(25.0, 25.0, None);
+    ext.source(10.0, 10.0, 1.0);
+    ext.controller(20.0, 20.0, 3, None, 1.0);
+    ext.construction_site(30.0, 30.0, StructureType::Road, 0, 100, 1.0);
+    ext.flush();
+    let visuals = &ext.room_visual.visuals;
+    assert!(matches!(visuals[visuals.len() - 1], RecordedVisual::Circle { x, .. } if x == 25.0));
+    // The road construction site goes into the roads layer, below the rest.
+    let first_x = match &visuals[0] {
+        RecordedVisual::Poly { points, .. } => points[0].0,
+        RecordedVisual::Circle { x, .. } => *x,
+        _ => 0.0,
+    };
+    assert!((first_x - 30.0).abs() < 0.5);
+}
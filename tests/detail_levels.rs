@@ -0,0 +1,87 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{DetailLevel, OverCapPolicy};
+use screeps::StructureType;
+
+const STRUCTURE_TYPES: [StructureType; 16] = [
+    StructureType::Spawn,
+    StructureType::Extension,
+    StructureType::Road,
+    StructureType::Wall,
+    StructureType::Rampart,
+    StructureType::Link,
+    StructureType::Storage,
+    StructureType::Tower,
+    StructureType::Observer,
+    StructureType::PowerSpawn,
+    StructureType::Extractor,
+    StructureType::Lab,
+    StructureType::Terminal,
+    StructureType::Container,
+    StructureType::Nuker,
+    StructureType::Factory,
+];
+
+#[test]
+fn simplified_structures() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Simplified);
+    for (i, structure_type) in STRUCTURE_TYPES.into_iter().enumerate() {
+        ext.structure(2.0 * i as f32 + 2.0, 25.0, structure_type, 1.0);
+    }
+    assert_snapshot("detail_simplified", &ext.room_visual.visuals);
+}
+
+#[test]
+fn minimal_structures() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Minimal);
+    for (i, structure_type) in STRUCTURE_TYPES.into_iter().enumerate() {
+        ext.structure(2.0 * i as f32 + 2.0, 25.0, structure_type, 1.0);
+    }
+    assert_eq!(ext.room_visual.visuals.len(), STRUCTURE_TYPES.len());
+    assert_snapshot("detail_minimal", &ext.room_visual.visuals);
+}
+
+#[test]
+fn simplified_structures_use_at_most_two_primitives() {
+    for structure_type in STRUCTURE_TYPES {
+        let mut ext = recording_ext();
+        ext.set_detail_level(DetailLevel::Simplified);
+        ext.structure(25.0, 25.0, structure_type, 1.0);
+        assert!(
+            ext.room_visual.visuals.len() <= 2,
+            "{structure_type:?} uses {} primitives",
+            ext.room_visual.visuals.len()
+        );
+    }
+}
+
+#[test]
+fn auto_detail_lowers_level_with_used_budget() {
+    let mut ext = recording_ext();
+    ext.set_size_cap(10000, OverCapPolicy::Reject);
+    ext.set_auto_detail(0.25, 0.75);
+    assert_eq!(ext.effective_detail_level(), DetailLevel::Full);
+
+    let mut levels = Vec::new();
+    for x in 0..100 {
+        levels.push(ext.effective_detail_level());
+        ext.structure((x % 50) as f32, 10.0, StructureType::Factory, 1.0);
+    }
+    assert!(levels.contains(&DetailLevel::Simplified));
+    assert_eq!(levels.last(), Some(&DetailLevel::Minimal));
+    assert!(levels.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    ext.clear_auto_detail();
+    assert_eq!(ext.effective_detail_level(), DetailLevel::Full);
+}
+
+#[test]
+fn manual_level_is_a_lower_bound() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Simplified);
+    ext.set_auto_detail(0.5, 0.9);
+    assert_eq!(ext.effective_detail_level(), DetailLevel::Simplified);
+}
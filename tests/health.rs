@@ -0,0 +1,94 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext, texts};
+use room_visual_ext::Theme;
+use screeps::StructureType;
+
+#[test]
+fn health_bars() {
+    let mut ext = recording_ext();
+    for (i, hits) in [3000, 2250, 1500, 750, 0].into_iter().enumerate() {
+        ext.health_bar(10.0 + i as f32, 10.0, hits, 3000, 1.0);
+    }
+    assert_snapshot("health_bars", &ext.room_visual.visuals);
+}
+
+#[test]
+fn health_bar_colors_follow_the_theme() {
+    let theme = Theme::game();
+    let mut ext = recording_ext();
+    for hits in [100, 50, 1] {
+        ext.health_bar(10.0, 10.0, hits, 100, 1.0);
+    }
+    let fills = ext
+        .room_visual
+        .visuals
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|visual| visual.fill().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(fills[..2], [theme.health_high, theme.health_medium]);
+    assert_ne!(fills[2], theme.health_low);
+}
+
+#[test]
+fn walls_and_ramparts_show_absolute_hits() {
+    let mut ext = recording_ext();
+    ext.structure_with_health(
+        10.0,
+        10.0,
+        StructureType::Rampart,
+        12_345_678,
+        300_000_000,
+        1.0,
+    );
+    ext.structure_with_health(
+        11.0,
+        10.0,
+        StructureType::Wall,
+        150_000_000,
+        300_000_000,
+        1.0,
+    );
+    ext.hits_amount(12.0, 10.0, 4_567, 1.0);
+    ext.hits_amount(13.0, 10.0, 950, 1.0);
+    assert_eq!(
+        texts(&ext.room_visual.visuals),
+        ["12.3M", "150M", "4.5k", "950"]
+    );
+}
+
+#[test]
+fn hits_are_rounded_down() {
+    let mut ext = recording_ext();
+    for hits in [999, 1_000, 9_999, 99_999, 999_999, 1_000_000, 1_000_000_000] {
+        ext.hits_amount(10.0, 10.0, hits, 1.0);
+    }
+    assert_eq!(
+        texts(&ext.room_visual.visuals),
+        ["999", "1k", "9.9k", "99.9k", "999k", "1M", "1000M"]
+    );
+}
+
+#[test]
+fn other_structures_get_health_bars() {
+    let mut ext = recording_ext();
+    ext.structure_with_health(10.0, 10.0, StructureType::Spawn, 2500, 5000, 1.0);
+    let mut expected = recording_ext();
+    expected.structure(10.0, 10.0, StructureType::Spawn, 1.0);
+    expected.health_bar(10.0, 10.0, 2500, 5000, 1.0);
+    assert_eq!(ext.room_visual.visuals, expected.room_visual.visuals);
+}
+
+#[test]
+fn health_is_drawn_above_ramparts_in_deferred_mode() {
+    let mut ext = recording_ext();
+    ext.set_deferred(true);
+    ext.health_bar(10.0, 10.0, 50, 100, 1.0);
+    ext.structure(10.0, 10.0, StructureType::Rampart, 1.0);
+    ext.flush();
+    let visuals = &ext.room_visual.visuals;
+    assert_eq!(visuals.len(), 3);
+    assert_eq!(visuals[0].fill(), Some(Theme::game().rampart.as_str()));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{recording_ext, xy};
+use room_visual_ext::RecordedVisual;
+use screeps::StructureType;
+
+#[test]
+fn layout_is_drawn_in_layer_order() {
+    let layout = [
+        (xy(11, 10), StructureType::Rampart),
+        (xy(11, 10), StructureType::Spawn),
+        (xy(12, 10), StructureType::Container),
+        (xy(12, 10), StructureType::Road),
+        (xy(11, 11), StructureType::Road),
+        (xy(13, 10), StructureType::Extension),
+    ];
+
+    let mut ext = recording_ext();
+    ext.structures(layout, 1.0);
+
+    let mut expected = recording_ext();
+    for (xy, structure_type) in [
+        (xy(12, 10), StructureType::Road),
+        (xy(11, 11), StructureType::Road),
+        (xy(11, 10), StructureType::Spawn),
+        (xy(13, 10), StructureType::Extension),
+        (xy(12, 10), StructureType::Container),
+        (xy(11, 10), StructureType::Rampart),
+    ] {
+        expected.structure_roomxy(xy, structure_type, 1.0);
+    }
+
+    assert_eq!(ext.room_visual.visuals, expected.room_visual.visuals);
+}
+
+#[test]
+fn layout_order_does_not_matter() {
+    // One structure per layer, so that the order within layers does not matter either.
+    let layout = [
+        (xy(20, 20), StructureType::Tower),
+        (xy(21, 20), StructureType::Road),
+        (xy(21, 20), StructureType::Rampart),
+        (xy(22, 20), StructureType::Container),
+    ];
+
+    let mut ext = recording_ext();
+    ext.structures(layout, 1.0);
+    let mut reversed_ext = recording_ext();
+    reversed_ext.structures(layout.into_iter().rev(), 1.0);
+
+    assert_eq!(ext.room_visual.visuals, reversed_ext.room_visual.visuals);
+}
+
+#[test]
+fn road_connections_are_below_containers_and_ramparts() {
+    let layout = [
+        (xy(30, 30), StructureType::Container),
+        (xy(30, 30), StructureType::Rampart),
+        (xy(30, 30), StructureType::Road),
+        (xy(31, 30), StructureType::Road),
+    ];
+
+    let mut ext = recording_ext();
+    ext.structures(layout, 1.0);
+
+    let visuals = &ext.room_visual.visuals;
+    let last_line = visuals
+        .iter()
+        .rposition(|visual| matches!(visual, RecordedVisual::Line { .. }))
+        .unwrap();
+    let first_rect = visuals
+        .iter()
+        .position(|visual| matches!(visual, RecordedVisual::Rect { .. }))
+        .unwrap();
+    assert!(last_line < first_rect);
+}
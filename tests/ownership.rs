@@ -0,0 +1,82 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext, strokes, xy};
+use room_visual_ext::{Ownership, Theme};
+use screeps::StructureType;
+
+#[test]
+fn hostile_structures() {
+    let mut ext = recording_ext();
+    ext.set_ownership(Ownership::Hostile);
+    ext.structure(10.0, 10.0, StructureType::Spawn, 1.0);
+    ext.structure(12.0, 10.0, StructureType::Tower, 1.0);
+    ext.structure(14.0, 10.0, StructureType::Lab, 1.0);
+    assert_snapshot("ownership_hostile", &ext.room_visual.visuals);
+}
+
+#[test]
+fn own_spawn_keeps_its_light_ring() {
+    let mut ext = recording_ext();
+    ext.structure(10.0, 10.0, StructureType::Spawn, 1.0);
+    ext.structure_owned(12.0, 10.0, StructureType::Spawn, Ownership::Ally, 1.0);
+    assert_eq!(strokes(&ext.room_visual.visuals), ["#CCCCCC", "#6BA6E0"]);
+}
+
+#[test]
+fn structure_owned_keeps_the_current_ownership() {
+    let mut ext = recording_ext();
+    ext.set_ownership(Ownership::Neutral);
+    ext.structure_owned(
+        10.0,
+        10.0,
+        StructureType::Extension,
+        Ownership::Hostile,
+        1.0,
+    );
+    ext.structure(12.0, 10.0, StructureType::Extension, 1.0);
+    assert_eq!(ext.ownership(), &Ownership::Neutral);
+    assert_eq!(strokes(&ext.room_visual.visuals), ["#E05C5C", "#A8A8A8"]);
+}
+
+#[test]
+fn players_have_their_own_colors() {
+    let mut theme = Theme::game();
+    theme
+        .player_outlines
+        .insert("Invader".to_string(), "#FF8800".to_string());
+    let mut ext = recording_ext();
+    ext.set_theme(theme);
+    for username in ["Invader", "Someone"] {
+        let ownership = Ownership::Player(username.to_string());
+        ext.structure_owned(10.0, 10.0, StructureType::Extension, ownership, 1.0);
+    }
+    assert_eq!(strokes(&ext.room_visual.visuals), ["#FF8800", "#E05C5C"]);
+}
+
+#[test]
+fn outline_colors_of_theme() {
+    let theme = Theme::high_contrast();
+    assert_eq!(Ownership::Mine.outline_color(&theme), theme.outline);
+    assert_eq!(Ownership::Ally.outline_color(&theme), theme.ally_outline);
+    assert_eq!(
+        Ownership::Hostile.outline_color(&theme),
+        theme.hostile_outline
+    );
+    assert_eq!(
+        Ownership::Neutral.outline_color(&theme),
+        theme.neutral_outline
+    );
+}
+
+#[test]
+fn hostile_power_spawns_and_ramparts_are_tinted() {
+    let mut ext = recording_ext();
+    ext.set_ownership(Ownership::Hostile);
+    ext.structure(10.0, 10.0, StructureType::PowerSpawn, 1.0);
+    ext.structure(12.0, 10.0, StructureType::Rampart, 1.0);
+    ext.ramparts([xy(14, 10)], 1.0);
+    assert_eq!(
+        strokes(&ext.room_visual.visuals),
+        ["#E05C5C", "#E05C5C", "#E05C5C"]
+    );
+}
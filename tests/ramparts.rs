@@ -0,0 +1,79 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext, xy};
+use room_visual_ext::RecordedVisual;
+
+fn outlines(visuals: &[RecordedVisual]) -> Vec<&[(f32, f32)]> {
+    visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Poly { points, .. } => Some(points.as_slice()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn rampart_ring() {
+    let mut ext = recording_ext();
+    let ring = (10..13)
+        .flat_map(|y| (10..13).map(move |x| xy(x, y)))
+        .filter(|&tile| tile != xy(11, 11));
+    ext.ramparts(ring, 1.0);
+    assert_snapshot("ramparts_ring", &ext.room_visual.visuals);
+}
+
+#[test]
+fn rampart_line_uses_two_primitives() {
+    let mut ext = recording_ext();
+    ext.ramparts((5..45).map(|x| xy(x, 20)), 1.0);
+    let visuals = &ext.room_visual.visuals;
+    assert_eq!(visuals.len(), 2);
+    assert_eq!(
+        outlines(visuals),
+        [[
+            (4.5, 19.5),
+            (44.5, 19.5),
+            (44.5, 20.5),
+            (4.5, 20.5),
+            (4.5, 19.5)
+        ]
+        .as_slice()]
+    );
+}
+
+#[test]
+fn diagonal_ramparts_are_outlined_separately() {
+    let mut ext = recording_ext();
+    ext.ramparts([xy(10, 10), xy(11, 11), xy(12, 10)], 1.0);
+    let outlines = outlines(&ext.room_visual.visuals);
+    assert_eq!(outlines.len(), 3);
+    assert!(outlines.iter().all(|points| points.len() == 5));
+}
+
+#[test]
+fn ramparts_on_room_edges() {
+    let mut ext = recording_ext();
+    ext.ramparts([xy(0, 0), xy(1, 0), xy(49, 49)], 0.5);
+    assert_eq!(
+        outlines(&ext.room_visual.visuals),
+        [
+            [
+                (-0.5, -0.5),
+                (1.5, -0.5),
+                (1.5, 0.5),
+                (-0.5, 0.5),
+                (-0.5, -0.5)
+            ]
+            .as_slice(),
+            [
+                (48.5, 48.5),
+                (49.5, 48.5),
+                (49.5, 49.5),
+                (48.5, 49.5),
+                (48.5, 48.5)
+            ]
+            .as_slice()
+        ]
+    );
+}
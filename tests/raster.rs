@@ -0,0 +1,85 @@
+mod common;
+
+use common::recording_ext;
+use room_visual_ext::{Rasterizer, RoomVisualBackend};
+use screeps::{RectStyle, StructureType};
+
+#[test]
+fn background_is_plain() {
+    let image = Rasterizer::new().pixels_per_tile(2).render(&[]);
+    assert_eq!((image.width, image.height), (100, 100));
+    assert_eq!(image.pixel(0, 0), [0x2B, 0x2B, 0x2B, 255]);
+    assert_eq!(image.pixel(99, 99), [0x2B, 0x2B, 0x2B, 255]);
+}
+
+#[test]
+fn opaque_rect_covers_its_tile() {
+    let mut ext = recording_ext();
+    ext.room_visual.rect(
+        9.5,
+        9.5,
+        1.0,
+        1.0,
+        Some(RectStyle::default().fill("#FF0000").opacity(1.0)),
+    );
+    let image = Rasterizer::new()
+        .pixels_per_tile(4)
+        .render(&ext.room_visual.visuals);
+    for y in 40..44 {
+        for x in 40..44 {
+            assert_eq!(image.pixel(x, y), [255, 0, 0, 255]);
+        }
+    }
+    assert_eq!(image.pixel(39, 40), [0x2B, 0x2B, 0x2B, 255]);
+    assert_eq!(image.pixel(44, 43), [0x2B, 0x2B, 0x2B, 255]);
+}
+
+#[test]
+fn opacity_blends_with_background() {
+    let mut ext = recording_ext();
+    ext.room_visual.rect(
+        -0.5,
+        -0.5,
+        1.0,
+        1.0,
+        Some(RectStyle::default().fill("#FFFFFF").opacity(0.5)),
+    );
+    let image = Rasterizer::new()
+        .pixels_per_tile(4)
+        .background("#000000")
+        .render(&ext.room_visual.visuals);
+    assert_eq!(image.pixel(1, 1), [128, 128, 128, 255]);
+
+    let image = Rasterizer::new()
+        .pixels_per_tile(4)
+        .no_background()
+        .render(&ext.room_visual.visuals);
+    assert_eq!(image.pixel(1, 1), [255, 255, 255, 128]);
+    assert_eq!(image.pixel(5, 5), [0, 0, 0, 0]);
+}
+
+#[test]
+fn structure_is_drawn_with_its_colors() {
+    let mut ext = recording_ext();
+    ext.structure(25.0, 25.0, StructureType::Extension, 1.0);
+    let image = Rasterizer::new()
+        .pixels_per_tile(16)
+        .render(&ext.room_visual.visuals);
+    // Energy in the middle of the tile.
+    assert_eq!(image.pixel(408, 408), [0xFF, 0xE8, 0x7B, 255]);
+    // Corner of the tile outside of the extension.
+    assert_eq!(image.pixel(401, 401), [0x2B, 0x2B, 0x2B, 255]);
+}
+
+#[test]
+fn png_has_valid_header() {
+    let png = Rasterizer::new()
+        .pixels_per_tile(1)
+        .render(&[])
+        .encode_png();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!(&png[16..20], &50u32.to_be_bytes());
+    assert_eq!(&png[20..24], &50u32.to_be_bytes());
+    assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{DetailLevel, RecordedVisual};
+use screeps::ResourceType;
+use std::collections::HashSet;
+
+const OTHER_RESOURCES: [ResourceType; 42] = [
+    ResourceType::UtriumBar,
+    ResourceType::LemergiumBar,
+    ResourceType::ZynthiumBar,
+    ResourceType::KeaniumBar,
+    ResourceType::GhodiumMelt,
+    ResourceType::Oxidant,
+    ResourceType::Reductant,
+    ResourceType::Purifier,
+    ResourceType::Battery,
+    ResourceType::Composite,
+    ResourceType::Crystal,
+    ResourceType::Liquid,
+    ResourceType::Wire,
+    ResourceType::Switch,
+    ResourceType::Transistor,
+    ResourceType::Microchip,
+    ResourceType::Circuit,
+    ResourceType::Device,
+    ResourceType::Cell,
+    ResourceType::Phlegm,
+    ResourceType::Tissue,
+    ResourceType::Muscle,
+    ResourceType::Organoid,
+    ResourceType::Organism,
+    ResourceType::Alloy,
+    ResourceType::Tube,
+    ResourceType::Fixtures,
+    ResourceType::Frame,
+    ResourceType::Hydraulics,
+    ResourceType::Machine,
+    ResourceType::Condensate,
+    ResourceType::Concentrate,
+    ResourceType::Extract,
+    ResourceType::Spirit,
+    ResourceType::Emanation,
+    ResourceType::Essence,
+    ResourceType::Silicon,
+    ResourceType::Metal,
+    ResourceType::Biomass,
+    ResourceType::Mist,
+    ResourceType::Energy,
+    ResourceType::Power,
+];
+
+fn badge_texts(resource_type: ResourceType) -> Vec<String> {
+    let mut ext = recording_ext();
+    ext.resource(25.0, 25.0, resource_type, None, 1.0);
+    ext.room_visual
+        .visuals
+        .iter()
+        .filter_map(|visual| match visual {
+            RecordedVisual::Text { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn resource_badges() {
+    let mut ext = recording_ext();
+    let resource_types = [
+        ResourceType::Energy,
+        ResourceType::Power,
+        ResourceType::Hydrogen,
+        ResourceType::Catalyst,
+        ResourceType::Hydroxide,
+        ResourceType::UtriumHydride,
+        ResourceType::CatalyzedGhodiumAlkalide,
+        ResourceType::Ops,
+        ResourceType::KeaniumBar,
+        ResourceType::Wire,
+        ResourceType::Mist,
+    ];
+    for (i, resource_type) in resource_types.into_iter().enumerate() {
+        ext.resource(2.0 * i as f32 + 10.0, 25.0, resource_type, None, 1.0);
+    }
+    assert_snapshot("resource_badges", &ext.room_visual.visuals);
+}
+
+#[test]
+fn compounds_are_labeled_with_formulas() {
+    assert_eq!(badge_texts(ResourceType::Hydroxide), ["OH"]);
+    assert_eq!(badge_texts(ResourceType::CatalyzedUtriumAcid), ["XUH₂O"]);
+    assert_eq!(badge_texts(ResourceType::Ghodium), ["G"]);
+}
+
+#[test]
+fn other_resources_have_distinct_labels() {
+    let labels = OTHER_RESOURCES
+        .into_iter()
+        .map(badge_texts)
+        .collect::<Vec<_>>();
+    assert!(labels[..OTHER_RESOURCES.len() - 2]
+        .iter()
+        .all(|texts| texts.len() == 1));
+    assert_eq!(
+        labels.iter().collect::<HashSet<_>>().len(),
+        OTHER_RESOURCES.len() - 1
+    );
+}
+
+#[test]
+fn amount_is_written_below() {
+    let mut ext = recording_ext();
+    ext.resource(25.0, 25.0, ResourceType::Energy, Some(123_456), 1.0);
+    assert_snapshot("resource_badge_amount", &ext.room_visual.visuals);
+}
+
+#[test]
+fn minimal_badges_are_single_primitives() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Minimal);
+    ext.resource(25.0, 25.0, ResourceType::Utrium, Some(100), 1.0);
+    ext.resource(26.0, 25.0, ResourceType::UtriumAcid, Some(100), 1.0);
+    ext.resource(27.0, 25.0, ResourceType::Wire, Some(100), 1.0);
+    assert_eq!(ext.room_visual.visuals.len(), 3);
+}
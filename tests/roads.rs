@@ -0,0 +1,91 @@
+mod common;
+
+use common::{assert_snapshot, connections, recording_ext, xy};
+use room_visual_ext::DetailLevel;
+use screeps::StructureType;
+
+#[test]
+fn connections_do_not_depend_on_drawing_order() {
+    let roads = [(10, 10), (11, 10), (12, 11), (11, 12), (20, 20)];
+
+    let mut ext = recording_ext();
+    for (x, y) in roads {
+        ext.structure(x as f32, y as f32, StructureType::Road, 1.0);
+    }
+    let mut reversed_ext = recording_ext();
+    for (x, y) in roads.into_iter().rev() {
+        reversed_ext.structure(x as f32, y as f32, StructureType::Road, 1.0);
+    }
+
+    let connections = connections(&ext.room_visual.visuals);
+    assert_eq!(connections.len(), 3);
+    assert_eq!(
+        connections,
+        self::connections(&reversed_ext.room_visual.visuals)
+    );
+}
+
+#[test]
+fn connections_have_lower_opacity() {
+    let mut ext = recording_ext();
+    ext.structure(10.0, 10.0, StructureType::Road, 0.4);
+    ext.structure(11.0, 10.0, StructureType::Road, 1.0);
+    assert_eq!(
+        connections(&ext.room_visual.visuals),
+        [((10.0, 10.0), (11.0, 10.0), 0.4)]
+    );
+}
+
+#[test]
+fn minimal_roads_connect() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Minimal);
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(11.0, 11.0, StructureType::Road, 1.0);
+    assert_eq!(
+        connections(&ext.room_visual.visuals),
+        [((10.0, 10.0), (11.0, 11.0), 1.0)]
+    );
+}
+
+#[test]
+fn roads_connect_to_existing_roads() {
+    let mut ext = recording_ext();
+    ext.add_existing_roads([xy(0, 10), xy(1, 11), xy(5, 5)]);
+    ext.structure(0.0, 11.0, StructureType::Road, 0.5);
+    assert_snapshot("roads_existing", &ext.room_visual.visuals);
+}
+
+#[test]
+fn redrawn_roads_are_connected_once() {
+    let mut ext = recording_ext();
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(11.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(12.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(11.0, 10.0, StructureType::Road, 1.0);
+    assert_eq!(
+        connections(&ext.room_visual.visuals),
+        [
+            ((10.0, 10.0), (11.0, 10.0), 1.0),
+            ((11.0, 10.0), (12.0, 10.0), 1.0)
+        ]
+    );
+}
+
+#[test]
+fn redrawn_roads_keep_drawn_connections() {
+    let mut ext = recording_ext();
+    ext.structure(10.0, 10.0, StructureType::Road, 0.4);
+    ext.structure(11.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(10.0, 11.0, StructureType::Road, 1.0);
+    assert_eq!(
+        connections(&ext.room_visual.visuals),
+        [
+            ((10.0, 10.0), (10.0, 11.0), 1.0),
+            ((10.0, 10.0), (11.0, 10.0), 0.4),
+            ((11.0, 10.0), (10.0, 11.0), 1.0)
+        ]
+    );
+}
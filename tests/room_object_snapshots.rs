@@ -0,0 +1,60 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext, texts};
+use room_visual_ext::DetailLevel;
+use screeps::ResourceType;
+
+#[test]
+fn source() {
+    let mut ext = recording_ext();
+    ext.source(25.0, 25.0, 1.0);
+    assert_snapshot("source", &ext.room_visual.visuals);
+}
+
+#[test]
+fn minerals() {
+    let mut ext = recording_ext();
+    let mineral_types = [
+        ResourceType::Hydrogen,
+        ResourceType::Oxygen,
+        ResourceType::Utrium,
+        ResourceType::Lemergium,
+        ResourceType::Keanium,
+        ResourceType::Zynthium,
+        ResourceType::Catalyst,
+    ];
+    for (i, mineral_type) in mineral_types.into_iter().enumerate() {
+        ext.mineral(2.0 * i as f32 + 10.0, 25.0, mineral_type, 1.0);
+    }
+    assert_eq!(
+        texts(&ext.room_visual.visuals),
+        ["H", "O", "U", "L", "K", "Z", "X"]
+    );
+    assert_snapshot("minerals", &ext.room_visual.visuals);
+}
+
+#[test]
+fn deposits() {
+    let mut ext = recording_ext();
+    let deposit_types = [
+        ResourceType::Silicon,
+        ResourceType::Metal,
+        ResourceType::Biomass,
+        ResourceType::Mist,
+    ];
+    for (i, deposit_type) in deposit_types.into_iter().enumerate() {
+        ext.deposit(2.0 * i as f32 + 10.0, 25.0, deposit_type, 1.0);
+    }
+    assert_eq!(texts(&ext.room_visual.visuals), ["Si", "Me", "Bi", "Mi"]);
+    assert_snapshot("deposits", &ext.room_visual.visuals);
+}
+
+#[test]
+fn minimal_room_objects_are_single_primitives() {
+    let mut ext = recording_ext();
+    ext.set_detail_level(DetailLevel::Minimal);
+    ext.source(10.0, 10.0, 1.0);
+    ext.mineral(12.0, 10.0, ResourceType::Utrium, 1.0);
+    ext.deposit(14.0, 10.0, ResourceType::Mist, 1.0);
+    assert_eq!(ext.room_visual.visuals.len(), 3);
+}
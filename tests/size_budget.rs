@@ -0,0 +1,77 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{estimated_size, OverCapPolicy, ROOM_VISUAL_SIZE_LIMIT};
+use screeps::{CircleStyle, StructureType, Visual};
+
+#[test]
+fn size_is_estimated_like_in_js() {
+    assert_eq!(
+        estimated_size(&Visual::circle(25.0, 25.0, None)),
+        r#"{"t":"c","x":25,"y":25}"#.len() + 1
+    );
+    // 0.65f32 is formatted by JS as 0.6499999761581421.
+    assert_eq!(
+        estimated_size(&Visual::circle(
+            25.0,
+            25.0,
+            Some(CircleStyle::default().radius(0.65))
+        )),
+        r#"{"t":"c","x":25,"y":25,"s":{"radius":0.6499999761581421}}"#.len() + 1
+    );
+}
+
+#[test]
+fn drawn_primitives_are_accounted() {
+    let mut ext = recording_ext();
+    ext.circle(25.0, 25.0, None);
+    assert_eq!(ext.size_budget().used(), 24);
+    ext.structure(20.0, 20.0, StructureType::Spawn, 1.0);
+    assert_eq!(ext.room_visual.visuals.len(), 3);
+    assert!(ext.size_budget().used() > 3 * 24);
+    assert_eq!(
+        ext.size_budget().remaining(),
+        ROOM_VISUAL_SIZE_LIMIT - ext.size_budget().used()
+    );
+    assert_eq!(ext.size_budget().rejected(), 0);
+}
+
+#[test]
+fn reject_policy_stops_drawing_at_cap() {
+    let mut ext = recording_ext();
+    ext.set_size_cap(1000, OverCapPolicy::Reject);
+    for x in 0..50 {
+        ext.structure(x as f32, 10.0, StructureType::Extension, 1.0);
+    }
+    let budget = ext.size_budget();
+    assert!(budget.used() <= 1000);
+    assert!(budget.rejected() > 0);
+    assert_eq!(ext.room_visual.visuals.len() + budget.rejected(), 2 * 50);
+}
+
+#[test]
+fn downgrade_policy_draws_markers_over_cap() {
+    let mut ext = recording_ext();
+    ext.set_size_cap(1, OverCapPolicy::Downgrade);
+    ext.structure(25.0, 25.0, StructureType::Factory, 1.0);
+    ext.structure(26.0, 25.0, StructureType::Factory, 1.0);
+    assert_eq!(ext.size_budget().rejected(), 0);
+    assert!(ext.size_budget().is_over_cap());
+    assert_eq!(ext.room_visual.visuals.len(), 9);
+    assert_snapshot("downgraded_factory", &ext.room_visual.visuals[8..]);
+}
+
+#[test]
+fn size_is_attributed_to_categories() {
+    let mut ext = recording_ext();
+    ext.set_size_category("planner");
+    ext.structure(25.0, 25.0, StructureType::Storage, 1.0);
+    ext.set_size_category("defense");
+    ext.set_size_cap(ext.size_budget().used(), OverCapPolicy::Reject);
+    ext.structure(25.0, 25.0, StructureType::Rampart, 1.0);
+
+    let budget = ext.size_budget();
+    assert_eq!(budget.used_by_category()["planner"], budget.used());
+    assert!(!budget.used_by_category().contains_key("defense"));
+    assert_eq!(budget.rejected_by_category()["defense"], 1);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::{RecordedVisual, SpeechStyle};
+
+fn bubble_points(visuals: &[RecordedVisual]) -> &[(f32, f32)] {
+    match &visuals[0] {
+        RecordedVisual::Poly { points, .. } => points,
+        visual => panic!("expected the bubble, got {visual}"),
+    }
+}
+
+#[test]
+fn speech_bubble() {
+    let mut ext = recording_ext();
+    ext.speech(25.0, 25.0, "harvester", None);
+    assert_snapshot("speech_bubble", &ext.room_visual.visuals);
+}
+
+#[test]
+fn multi_line_speech_bubble() {
+    let mut ext = recording_ext();
+    let style = SpeechStyle::default()
+        .background("#FFFFFF")
+        .color("#FF0000")
+        .font_size(0.4)
+        .opacity(0.8);
+    ext.speech(25.0, 25.0, "hauler\nto storage", Some(style));
+    assert_snapshot("speech_bubble_multi_line", &ext.room_visual.visuals);
+}
+
+#[test]
+fn bubble_is_flipped_at_room_edge() {
+    let mut ext = recording_ext();
+    ext.speech(0.0, 0.0, "attack", None);
+    assert!(bubble_points(&ext.room_visual.visuals)
+        .iter()
+        .any(|&(x, y)| x < -0.5 || y < -0.5));
+
+    let mut ext = recording_ext();
+    let style = SpeechStyle::default().flip_at_room_edge(true);
+    ext.speech(0.0, 0.0, "attack", Some(style));
+    let points = bubble_points(&ext.room_visual.visuals);
+    assert!(points.iter().all(|&(x, y)| x >= -0.5 && y >= -0.5));
+    // The tail still points at the tile.
+    assert!(points.contains(&(0.0, 0.3)));
+    assert_snapshot("speech_bubble_flipped", &ext.room_visual.visuals);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext, texts};
+use room_visual_ext::RecordedVisual;
+use screeps::StructureType;
+
+macro_rules! structure_snapshot {
+    ($name:ident, $structure_type:expr) => {
+        #[test]
+        fn $name() {
+            let mut ext = recording_ext();
+            ext.structure(25.0, 25.0, $structure_type, 1.0);
+            assert_snapshot(
+                concat!("structure_", stringify!($name)),
+                &ext.room_visual.visuals,
+            );
+        }
+    };
+}
+
+structure_snapshot!(spawn, StructureType::Spawn);
+structure_snapshot!(extension, StructureType::Extension);
+structure_snapshot!(road, StructureType::Road);
+structure_snapshot!(wall, StructureType::Wall);
+structure_snapshot!(rampart, StructureType::Rampart);
+structure_snapshot!(link, StructureType::Link);
+structure_snapshot!(storage, StructureType::Storage);
+structure_snapshot!(tower, StructureType::Tower);
+structure_snapshot!(observer, StructureType::Observer);
+structure_snapshot!(power_spawn, StructureType::PowerSpawn);
+structure_snapshot!(extractor, StructureType::Extractor);
+structure_snapshot!(lab, StructureType::Lab);
+structure_snapshot!(terminal, StructureType::Terminal);
+structure_snapshot!(container, StructureType::Container);
+structure_snapshot!(nuker, StructureType::Nuker);
+structure_snapshot!(factory, StructureType::Factory);
+structure_snapshot!(controller, StructureType::Controller);
+structure_snapshot!(keeper_lair, StructureType::KeeperLair);
+structure_snapshot!(portal, StructureType::Portal);
+structure_snapshot!(power_bank, StructureType::PowerBank);
+structure_snapshot!(invader_core, StructureType::InvaderCore);
+
+#[test]
+fn connected_roads() {
+    let mut ext = recording_ext();
+    ext.structure(25.0, 25.0, StructureType::Road, 1.0);
+    ext.structure(26.0, 25.0, StructureType::Road, 1.0);
+    ext.structure(26.0, 26.0, StructureType::Road, 0.5);
+    assert_snapshot("connected_roads", &ext.room_visual.visuals);
+}
+
+#[test]
+fn controller_levels_and_progress() {
+    let mut ext = recording_ext();
+    ext.controller(25.0, 25.0, 3, Some(0.25), 1.0);
+    assert_snapshot("controller_level_3", &ext.room_visual.visuals);
+
+    let mut ext = recording_ext();
+    ext.controller(25.0, 25.0, 8, None, 1.0);
+    let segments = ext
+        .room_visual
+        .visuals
+        .iter()
+        .filter(|visual| matches!(visual, RecordedVisual::Poly { .. }))
+        .count();
+    assert_eq!(segments, 8);
+}
+
+#[test]
+fn half_opacity() {
+    let mut ext = recording_ext();
+    ext.structure(25.0, 25.0, StructureType::Spawn, 0.5);
+    ext.structure(25.0, 25.0, StructureType::Rampart, 0.5);
+    assert_snapshot("half_opacity", &ext.room_visual.visuals);
+}
+
+#[test]
+fn neutral_structures_with_state() {
+    let mut ext = recording_ext();
+    ext.keeper_lair(10.0, 10.0, Some(42), 1.0);
+    ext.portal(12.0, 10.0, Some(25_000), 1.0);
+    ext.power_bank(14.0, 10.0, Some(4_567), 1.0);
+    ext.invader_core(16.0, 10.0, Some(3), 1.0);
+    assert_snapshot("neutral_structures_with_state", &ext.room_visual.visuals);
+
+    assert_eq!(texts(&ext.room_visual.visuals), ["42", "25k", "4.5k", "3"]);
+}
+
+#[test]
+fn amounts_are_rounded_down() {
+    let mut ext = recording_ext();
+    for power in [999, 1_000, 9_999, 999_999, 1_000_000, 1_000_000_000] {
+        ext.power_bank(10.0, 10.0, Some(power), 1.0);
+    }
+    assert_eq!(
+        texts(&ext.room_visual.visuals),
+        ["999", "1k", "9.9k", "999k", "1M", "1000M"]
+    );
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::{assert_file_snapshot, recording_ext};
+use room_visual_ext::{RoomVisualBackend, SvgRenderer};
+use screeps::{StructureType, TextStyle};
+
+#[test]
+fn svg_of_structures() {
+    let mut ext = recording_ext();
+    ext.structure(24.0, 25.0, StructureType::Road, 1.0);
+    ext.structure(25.0, 25.0, StructureType::Road, 1.0);
+    ext.structure(25.0, 25.0, StructureType::Container, 1.0);
+    ext.structure(24.0, 24.0, StructureType::Link, 1.0);
+    ext.structure(24.0, 24.0, StructureType::Rampart, 1.0);
+    ext.room_visual.text(
+        25.0,
+        23.0,
+        "<plan & notes>".into(),
+        Some(TextStyle::default()),
+    );
+
+    let svg = SvgRenderer::new()
+        .pixels_per_tile(8.0)
+        .render(&ext.room_visual.visuals);
+    assert_file_snapshot("svg_of_structures.svg", &svg);
+}
+
+#[test]
+fn svg_without_background() {
+    let svg = SvgRenderer::new().no_background().render(&[]);
+    assert_eq!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"800\" viewBox=\"-0.5 -0.5 50 50\">\n</svg>\n"
+    );
+}
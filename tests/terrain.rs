@@ -0,0 +1,84 @@
+mod common;
+
+use common::{assert_snapshot, recording_ext};
+use room_visual_ext::RecordedVisual;
+use screeps::Terrain;
+
+type TerrainGrid = [[Terrain; 50]; 50];
+
+fn sample_terrain() -> TerrainGrid {
+    let mut terrain = [[Terrain::Plain; 50]; 50];
+    terrain[0].fill(Terrain::Wall);
+    terrain[49].fill(Terrain::Wall);
+    for row in terrain.iter_mut() {
+        row[0] = Terrain::Wall;
+        row[49] = Terrain::Wall;
+    }
+    for row in terrain.iter_mut().take(30).skip(20) {
+        row[10..15].fill(Terrain::Swamp);
+    }
+    // Irregular features, so that the merging is not trivial.
+    for (y, row) in terrain.iter_mut().enumerate().take(49).skip(1) {
+        for (x, tile) in row.iter_mut().enumerate().take(49).skip(1) {
+            if (x * 7 + y * 13) % 31 == 0 {
+                *tile = Terrain::Wall;
+            } else if (x * y) % 17 == 3 {
+                *tile = Terrain::Swamp;
+            }
+        }
+    }
+    terrain
+}
+
+#[test]
+fn terrain_layer() {
+    let mut terrain = [[Terrain::Plain; 50]; 50];
+    terrain[0].fill(Terrain::Wall);
+    for row in terrain.iter_mut().take(8).skip(5) {
+        row[3..6].fill(Terrain::Swamp);
+    }
+    terrain[6][4] = Terrain::Wall;
+    let mut ext = recording_ext();
+    ext.terrain(&terrain, 1.0);
+    assert_snapshot("terrain_layer", &ext.room_visual.visuals);
+}
+
+#[test]
+fn merged_rects_cover_the_terrain_exactly() {
+    let terrain = sample_terrain();
+    let mut ext = recording_ext();
+    ext.terrain(&terrain, 1.0);
+
+    let mut drawn = [[Terrain::Plain; 50]; 50];
+    for visual in &ext.room_visual.visuals {
+        let RecordedVisual::Rect {
+            x,
+            y,
+            width,
+            height,
+            ..
+        } = visual
+        else {
+            panic!("expected only rects, got {visual}");
+        };
+        let terrain_type = match visual.fill() {
+            Some("#111111") => Terrain::Wall,
+            _ => Terrain::Swamp,
+        };
+        let (left, top) = ((x + 0.5) as usize, (y + 0.5) as usize);
+        for row in drawn.iter_mut().skip(top).take(*height as usize) {
+            for tile in row.iter_mut().skip(left).take(*width as usize) {
+                assert_eq!(*tile, Terrain::Plain, "rects overlap");
+                *tile = terrain_type;
+            }
+        }
+    }
+    assert_eq!(drawn, terrain);
+
+    let tiles = terrain
+        .iter()
+        .flatten()
+        .filter(|&&tile| tile != Terrain::Plain)
+        .count();
+    assert!(ext.room_visual.visuals.len() < tiles / 2);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{assert_snapshot, colors, recording_ext};
+use room_visual_ext::{AnimatedPositionStyle, SpeechStyle, Theme};
+use screeps::{Part, ResourceType, StructureType};
+
+#[test]
+fn game_theme_is_the_default() {
+    let ext = recording_ext();
+    assert_eq!(ext.theme(), &Theme::game());
+    assert_eq!(Theme::default(), Theme::game());
+}
+
+#[test]
+fn theme_applies_to_subsequent_draws() {
+    let mut ext = recording_ext();
+    ext.structure(10.0, 10.0, StructureType::Spawn, 1.0);
+    ext.set_theme(Theme::colorblind());
+    ext.structure(12.0, 10.0, StructureType::Spawn, 1.0);
+
+    let colors = colors(&ext.room_visual.visuals);
+    assert_eq!(colors[..3], ["#181818", "#CCCCCC", "#FFE87B"]);
+    assert_eq!(colors[3..], ["#181818", "#CCCCCC", "#F0E442"]);
+}
+
+#[test]
+fn custom_theme() {
+    let mut ext = recording_ext();
+    ext.set_theme(Theme {
+        road: "#FF00FF".to_string(),
+        ..Theme::game()
+    });
+    ext.structure(10.0, 10.0, StructureType::Road, 1.0);
+    ext.structure(11.0, 10.0, StructureType::Road, 1.0);
+    assert!(colors(&ext.room_visual.visuals)
+        .iter()
+        .all(|&color| color == "#FF00FF"));
+}
+
+#[test]
+fn monochrome_theme_uses_only_grays() {
+    let mut ext = recording_ext();
+    ext.set_theme(Theme::monochrome());
+    ext.structure(10.0, 10.0, StructureType::Factory, 1.0);
+    ext.structure(12.0, 10.0, StructureType::Rampart, 1.0);
+    ext.structure(14.0, 10.0, StructureType::PowerSpawn, 1.0);
+    ext.creep(16.0, 10.0, &[Part::Attack, Part::Heal, Part::Move], 1.0);
+    ext.resource(18.0, 10.0, ResourceType::CatalyzedGhodiumAcid, None, 1.0);
+    ext.resource(20.0, 10.0, ResourceType::Utrium, None, 1.0);
+
+    for color in colors(&ext.room_visual.visuals) {
+        let bytes = color.as_bytes();
+        assert!(
+            color.len() == 7 && bytes[1..3] == bytes[3..5] && bytes[3..5] == bytes[5..7],
+            "{color} is not gray"
+        );
+    }
+}
+
+#[test]
+fn high_contrast_theme() {
+    let mut ext = recording_ext();
+    ext.set_theme(Theme::high_contrast());
+    ext.structure(10.0, 10.0, StructureType::Extension, 1.0);
+    ext.structure(11.0, 10.0, StructureType::Tower, 1.0);
+    ext.resource(12.0, 10.0, ResourceType::Lemergium, None, 1.0);
+    assert_snapshot("theme_high_contrast", &ext.room_visual.visuals);
+}
+
+#[test]
+fn speech_and_position_markers_default_to_theme_colors() {
+    let mut ext = recording_ext();
+    ext.set_theme(Theme::colorblind());
+    ext.speech(10.0, 10.0, "hi", None);
+    ext.animated_position(12.0, 10.0, 0, None);
+    ext.speech(14.0, 10.0, "hi", Some(SpeechStyle::default().background("#123456")));
+    ext.animated_position(
+        16.0,
+        10.0,
+        0,
+        Some(AnimatedPositionStyle::default().color("#654321")),
+    );
+    assert_eq!(
+        colors(&ext.room_visual.visuals),
+        ["#009E73", "#000000", "#0072B2", "#123456", "#000000", "#654321"]
+    );
+}
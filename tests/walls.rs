@@ -0,0 +1,46 @@
+mod common;
+
+use common::{assert_snapshot, lines, recording_ext, xy};
+use screeps::StructureType;
+
+#[test]
+fn wall_block() {
+    let mut ext = recording_ext();
+    let block = (10..12).flat_map(|y| (10..13).map(move |x| xy(x, y)));
+    ext.walls(block.chain([xy(14, 10)]), 1.0);
+    assert_snapshot("walls_block", &ext.room_visual.visuals);
+}
+
+#[test]
+fn wall_regions_use_fewer_primitives() {
+    let bunker_walls = (5..45)
+        .flat_map(|i| [xy(i, 5), xy(i, 6), xy(i, 44), xy(5, i), xy(44, i)])
+        .collect::<Vec<_>>();
+
+    let mut ext = recording_ext();
+    ext.walls(bunker_walls.iter().copied(), 1.0);
+    let mut per_wall_ext = recording_ext();
+    for &xy in &bunker_walls {
+        per_wall_ext.structure_roomxy(xy, StructureType::Wall, 1.0);
+    }
+
+    let region_count = ext.room_visual.visuals.len();
+    assert!(region_count * 3 < per_wall_ext.room_visual.visuals.len());
+    assert!(ext.size_budget().used() * 3 < per_wall_ext.size_budget().used());
+}
+
+#[test]
+fn wall_stripes_span_whole_rows() {
+    let mut ext = recording_ext();
+    // The merged rectangles split the second row, but its stripes do not.
+    ext.walls([xy(10, 10), xy(10, 11), xy(11, 11), xy(12, 11)], 1.0);
+    assert_eq!(
+        lines(&ext.room_visual.visuals),
+        [
+            ((9.75, 9.85), (10.05, 9.85)),
+            ((9.95, 10.15), (10.25, 10.15)),
+            ((9.75, 10.85), (12.05, 10.85)),
+            ((9.95, 11.15), (12.25, 11.15)),
+        ]
+    );
+}